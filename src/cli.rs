@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use clap::Parser;
+use diagen::{DEFAULT_ATTRIBUTES, Format, model::VisibilityLevel};
 use log::LevelFilter;
 
 #[derive(Parser)]
@@ -13,6 +14,9 @@ pub struct Cli {
     /// Path to output the diagram
     #[clap(short, long)]
     pub output: Option<PathBuf>,
+    /// Output format of the diagram
+    #[clap(short, long, value_enum, default_value_t = Format::Html)]
+    pub format: Format,
     /// Log Level Filter [Debug, Info, Error, Warn]
     #[clap(short, long, default_value = "Info")]
     pub loglevel: LevelFilter,
@@ -22,4 +26,13 @@ pub struct Cli {
     /// Include test functions in the diagram (excluded by default)
     #[clap(short = 't', long, default_value = "false")]
     pub include_tests: bool,
+    /// Minimum visibility of the items, fields and methods to include
+    #[clap(long, value_enum, default_value_t = VisibilityLevel::Private)]
+    pub min_visibility: VisibilityLevel,
+    /// Comma-separated attributes to show on structs, enums, variants and functions, e.g. `repr` for `#[repr(C)]`
+    #[clap(long, value_delimiter = ',', default_values_t = DEFAULT_ATTRIBUTES.map(String::from))]
+    pub attributes: Vec<String>,
+    /// Show the methods of inherent impls on the struct and enum cards of the HTML page
+    #[clap(short, long, default_value = "false")]
+    pub group_methods: bool,
 }
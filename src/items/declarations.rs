@@ -0,0 +1,100 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use super::{ToHtml, doc_html};
+use crate::model::{Const, Static, TypeAlias, Visibility, abbreviate};
+
+const DECLARATION_TEMPLATE: &str = r#"
+    <div class="declaration">
+        <div class="declaration-signature"{{ if full_value }} title="{full_value}"{{ endif }}>
+            {{ if visibility }}<span class="declaration-visibility">{visibility}</span> {{ endif }}
+            <span class="declaration-keyword">{keyword}</span>
+            <span class="declaration-name">{name}</span>
+            {{ if ty }}<span class="declaration-type">: {ty}</span>{{ endif }}
+            <span class="declaration-value"> = {value}</span>
+        </div>
+        {{ if doc }}{doc | unescaped}{{ endif }}
+    </div>
+"#;
+
+/// A type alias, constant or static as a one-line declaration
+#[derive(Serialize)]
+pub struct DeclarationContext {
+    pub visibility: Option<String>,
+    /// `type`, `const`, `static` or `static mut`
+    pub keyword: String,
+    pub name: String,
+    pub ty: Option<String>,
+    /// The aliased type or the shortened value expression
+    pub value: String,
+    /// The whole value expression, shown on hover when it was shortened
+    pub full_value: Option<String>,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+}
+
+impl DeclarationContext {
+    fn value(visibility: &Visibility, keyword: &str, name: &str, ty: &str, value: &str) -> Self {
+        let short = abbreviate(value);
+        Self {
+            visibility: visibility.keyword(),
+            keyword: keyword.to_owned(),
+            name: name.to_owned(),
+            ty: Some(ty.to_owned()),
+            full_value: (short != value).then(|| value.to_owned()),
+            value: short,
+            doc: None,
+        }
+    }
+}
+
+impl From<&TypeAlias> for DeclarationContext {
+    fn from(alias: &TypeAlias) -> Self {
+        Self {
+            visibility: alias.visibility.keyword(),
+            keyword: "type".to_owned(),
+            name: format!(
+                "{}{}",
+                alias.name,
+                alias.generics.as_deref().unwrap_or_default()
+            ),
+            ty: None,
+            value: alias.ty.clone(),
+            full_value: None,
+            doc: doc_html(alias.doc.as_deref()),
+        }
+    }
+}
+
+impl From<&Const> for DeclarationContext {
+    fn from(c: &Const) -> Self {
+        Self {
+            doc: doc_html(c.doc.as_deref()),
+            ..Self::value(&c.visibility, "const", &c.name, &c.ty, &c.value)
+        }
+    }
+}
+
+impl From<&Static> for DeclarationContext {
+    fn from(s: &Static) -> Self {
+        let keyword = if s.is_mut { "static mut" } else { "static" };
+        Self {
+            doc: doc_html(s.doc.as_deref()),
+            ..Self::value(&s.visibility, keyword, &s.name, &s.ty, &s.value)
+        }
+    }
+}
+
+impl ToHtml for DeclarationContext {
+    fn to_html(&self) -> anyhow::Result<String> {
+        let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
+
+        tt.add_template("declaration", DECLARATION_TEMPLATE)
+            .context("Failed to add template")?;
+
+        tt.render("declaration", self)
+            .context("Failed to render template")
+    }
+}
@@ -2,41 +2,86 @@ use anyhow::Context as _;
 use serde::Serialize;
 use tinytemplate::TinyTemplate;
 
-use super::ToHtml;
+use super::{
+    BadgeContext, ToHtml, badges, doc_html,
+    members::{MEMBERS_TEMPLATE, MembersContext},
+};
+use crate::model::Enum;
 
 const ENUM_TEMPLATE: &str = r#"
     <div class="enum">
         <div class="enum-name">{name}</div>
+        {{ if badges }}<div class="item-badges">{{ for badge in badges }}<span class="item-badge {badge.class}" title="{badge.title}">{badge.text}</span>{{ endfor }}</div>{{ endif }}
+        {{ if doc }}{doc | unescaped}{{ endif }}
         <div class="enum-variants">
             {{ for variant in variants }}
-                <div class="enum-variant">
+                <div class="enum-variant"{{ if variant.doc }} title="{variant.doc}"{{ endif }}>
                     <div class="enum-variant-name">{variant.name}</div>
                     {{ if variant.data }}
                         <div class="enum-variant-data">{variant.data}</div>
                     {{ endif }}
+                    {{ if variant.discriminant }}
+                        <div class="enum-variant-discriminant">= {variant.discriminant}</div>
+                    {{ endif }}
+                    {{ for badge in variant.badges }}<span class="item-badge {badge.class}" title="{badge.title}">{badge.text}</span>{{ endfor }}
                 </div>
             {{ endfor }}
         </div>
+        {{ if members }}{{ call members with members }}{{ endif }}
     </div>
 "#;
 
 #[derive(Serialize)]
 pub struct EnumContext {
     pub name: String,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    pub badges: Vec<BadgeContext>,
     pub variants: Vec<EnumVariantContext>,
+    /// Items of the impl blocks when methods are grouped under their type
+    pub members: Option<MembersContext>,
 }
 
 #[derive(Serialize)]
 pub struct EnumVariantContext {
     pub name: String,
     pub data: Option<String>,
+    /// The explicit discriminant expression
+    pub discriminant: Option<String>,
+    /// The whole doc comment, shown on hover
+    pub doc: Option<String>,
+    pub badges: Vec<BadgeContext>,
+}
+
+impl From<&Enum> for EnumContext {
+    fn from(e: &Enum) -> Self {
+        Self {
+            name: format!("{}{}", e.name, e.generics.as_deref().unwrap_or_default()),
+            doc: doc_html(e.doc.as_deref()),
+            badges: badges(&e.derives, &e.attributes),
+            variants: e
+                .variants
+                .iter()
+                .map(|v| EnumVariantContext {
+                    name: v.name.clone(),
+                    data: v.data(),
+                    discriminant: v.discriminant.clone(),
+                    doc: v.doc.clone(),
+                    badges: badges(&[], &v.attributes),
+                })
+                .collect(),
+            members: None,
+        }
+    }
 }
 
 impl ToHtml for EnumContext {
     fn to_html(&self) -> anyhow::Result<String> {
         let mut tt = TinyTemplate::new();
-        tt.set_default_formatter(&tinytemplate::format_unescaped);
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
 
+        tt.add_template("members", MEMBERS_TEMPLATE)
+            .context("Failed to add template")?;
         tt.add_template("enum", ENUM_TEMPLATE)
             .context("Failed to add template")?;
 
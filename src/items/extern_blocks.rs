@@ -0,0 +1,73 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use super::{ToHtml, functions::FunctionContext};
+use crate::model::ExternBlock;
+
+const EXTERN_BLOCK_TEMPLATE: &str = r#"
+    <div class="extern-block">
+        <div class="extern-block-abi">extern "{abi}"</div>
+        {{ if types }}
+            <div class="extern-block-items">
+                {{ for ty in types }}
+                    <div class="extern-block-item"><span class="declaration-keyword">type</span> {ty}</div>
+                {{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if statics }}
+            <div class="extern-block-items">
+                {{ for static in statics }}
+                    <div class="extern-block-item">{static}</div>
+                {{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if functions }}
+            <div class="extern-block-functions">
+                {{ for function in functions }}
+                    {function | unescaped}
+                {{ endfor }}
+            </div>
+        {{ endif }}
+    </div>
+"#;
+
+#[derive(Serialize)]
+pub struct ExternBlockContext {
+    pub abi: String,
+    /// Opaque foreign types
+    pub types: Vec<String>,
+    /// Foreign statics as declared
+    pub statics: Vec<String>,
+    /// Rendered `FunctionContext`s
+    pub functions: Vec<String>,
+}
+
+impl ExternBlockContext {
+    /// Create an `ExternBlockContext` from a model `ExternBlock`, rendering its functions
+    pub fn new(block: &ExternBlock) -> anyhow::Result<Self> {
+        Ok(Self {
+            abi: block.abi.clone(),
+            types: block.types.clone(),
+            statics: block.statics.iter().map(ToString::to_string).collect(),
+            functions: block
+                .functions
+                .iter()
+                .map(|f| FunctionContext::new(f).to_html())
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
+
+impl ToHtml for ExternBlockContext {
+    fn to_html(&self) -> anyhow::Result<String> {
+        let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
+
+        tt.add_template("extern_block", EXTERN_BLOCK_TEMPLATE)
+            .context("Failed to add template")?;
+
+        tt.render("extern_block", self)
+            .context("Failed to render template")
+    }
+}
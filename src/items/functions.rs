@@ -1,79 +1,73 @@
 use anyhow::Context as _;
-use itertools::Itertools as _;
 use serde::Serialize;
-use syn::{Visibility, spanned::Spanned as _};
 use tinytemplate::TinyTemplate;
 
-use super::ToHtml;
+use super::{BadgeContext, ToHtml, badges, doc_html};
+use crate::model::Function;
+
+/// Signatures longer than this put every parameter on its own line
+const SIGNATURE_WIDTH: usize = 60;
 
 const FUNCTION_TEMPLATE: &str = r#"
     <div class="function">
         <div class="function-signature">
             {{ if visibility }}<span class="function-visibility">{visibility}</span> {{ endif }}
             {{ if modifiers }}<span class="function-modifiers">{modifiers}</span> {{ endif }}
-            <span class="function-name">{name}</span>
-            <span class="function-params">({params})</span>
+            <span class="function-name">{name}{{ if generics }}<span class="function-generics">{generics}</span>{{ endif }}</span>
+            <span class="function-params{{ if wrap }} function-params-wrapped{{ endif }}">({{ for param in params }}<span class="function-param">{param}{{ if not @last }}, {{ endif }}</span>{{ endfor }})</span>
             {{ if return_type }}<span class="function-return"> -> {return_type}</span>{{ endif }}
         </div>
+        {{ if where_clause }}
+            <div class="function-where">where
+                {{ for predicate in where_clause }}<span class="function-where-predicate">{predicate},</span>{{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if badges }}<div class="item-badges">{{ for badge in badges }}<span class="item-badge {badge.class}" title="{badge.title}">{badge.text}</span>{{ endfor }}</div>{{ endif }}
+        {{ if doc }}{doc | unescaped}{{ endif }}
     </div>
 "#;
 
 #[derive(Serialize)]
 pub struct FunctionContext {
     pub name: String,
-    pub params: String,
+    /// Generic parameters including their bounds
+    pub generics: Option<String>,
+    pub params: Vec<String>,
+    /// Whether the parameters are too long to fit on the line of the name
+    pub wrap: bool,
     pub return_type: Option<String>,
+    /// Predicates of the `where` clause
+    pub where_clause: Vec<String>,
     pub visibility: Option<String>,
     pub modifiers: Option<String>,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    pub badges: Vec<BadgeContext>,
 }
 
 impl FunctionContext {
-    /// Create a `FunctionContext` from a `syn::Signature` and attributes
-    pub fn new(sig: &syn::Signature, vis: &Visibility) -> Self {
-        let visibility = match vis {
-            Visibility::Public(_) => Some("pub".to_owned()),
-            Visibility::Restricted(_) | Visibility::Inherited => None,
-        };
-
-        let mut modifiers = Vec::new();
-        if sig.asyncness.is_some() {
-            modifiers.push("async".to_owned());
-        }
-        if sig.constness.is_some() {
-            modifiers.push("const".to_owned());
-        }
-        if sig.unsafety.is_some() {
-            modifiers.push("unsafe".to_owned());
-        }
-
-        let params = sig
-            .inputs
-            .iter()
-            .map(|param| match param {
-                syn::FnArg::Receiver(r) => {
-                    r.span().source_text().expect("Could not get source_text")
-                }
-                syn::FnArg::Typed(t) => t.span().source_text().expect("Could not get source_text"),
-            })
-            .join(", ");
-
-        let return_type = match &sig.output {
-            syn::ReturnType::Default => None,
-            syn::ReturnType::Type(_, ty) => {
-                Some(ty.span().source_text().expect("Could not get source_text"))
-            }
-        };
+    /// Create a `FunctionContext` from a model `Function`
+    pub fn new(function: &Function) -> Self {
+        let width = function.name.len()
+            + function.generics.as_ref().map_or(0, String::len)
+            + function
+                .params
+                .iter()
+                .map(|param| param.len() + 2)
+                .sum::<usize>()
+            + function.return_type.as_ref().map_or(0, |ty| ty.len() + 4);
 
         Self {
-            name: sig.ident.to_string(),
-            params,
-            return_type,
-            visibility,
-            modifiers: if modifiers.is_empty() {
-                None
-            } else {
-                Some(modifiers.join(" "))
-            },
+            name: function.name.clone(),
+            generics: function.generics.clone(),
+            params: function.params.clone(),
+            wrap: function.params.len() > 1 && width > SIGNATURE_WIDTH,
+            return_type: function.return_type.clone(),
+            where_clause: function.where_clause.clone(),
+            visibility: function.visibility.keyword(),
+            modifiers: function.modifiers(),
+            doc: doc_html(function.doc.as_deref()),
+            badges: badges(&[], &function.attributes),
         }
     }
 }
@@ -81,7 +75,7 @@ impl FunctionContext {
 impl ToHtml for FunctionContext {
     fn to_html(&self) -> anyhow::Result<String> {
         let mut tt = TinyTemplate::new();
-        tt.set_default_formatter(&tinytemplate::format_unescaped);
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
 
         tt.add_template("function", FUNCTION_TEMPLATE)
             .context("Failed to add template")?;
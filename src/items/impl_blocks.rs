@@ -10,11 +10,23 @@ const IMPL_TEMPLATE: &str = r#"
             <span class="impl-type">impl</span>
             {{ if generics }}<span class="impl-generics">{generics}</span>{{ endif }}
             {{ if trait_name }}<span class="impl-trait">{trait_name}</span>{{ endif }}
-            <span class="impl-target"> for {target_type}</span>
+            <span class="impl-target">{{ if trait_name }} for {{ endif }}{target_type}</span>
         </div>
+        {{ if where_clause }}
+            <div class="function-where impl-where">where
+                {{ for predicate in where_clause }}<span class="function-where-predicate">{predicate},</span>{{ endfor }}
+            </div>
+        {{ endif }}
         <div class="impl-content">
+            {{ if associated_items }}
+                <div class="impl-associated-items">
+                    {{ for item in associated_items }}
+                        <div class="impl-associated-item">{item}</div>
+                    {{ endfor }}
+                </div>
+            {{ endif }}
             {{ for function in functions }}
-                {function}
+                {function | unescaped}
             {{ endfor }}
         </div>
     </div>
@@ -25,13 +37,18 @@ pub struct ImplContext {
     pub target_type: String,
     pub trait_name: Option<String>,
     pub generics: Option<String>,
+    /// Predicates of the `where` clause
+    pub where_clause: Vec<String>,
+    /// Associated types and consts as declared
+    pub associated_items: Vec<String>,
+    /// Rendered `FunctionContext`s
     pub functions: Vec<String>,
 }
 
 impl ToHtml for ImplContext {
     fn to_html(&self) -> anyhow::Result<String> {
         let mut tt = TinyTemplate::new();
-        tt.set_default_formatter(&tinytemplate::format_unescaped);
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
 
         tt.add_template("impl", IMPL_TEMPLATE)
             .context("Failed to add template")?;
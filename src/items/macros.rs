@@ -0,0 +1,54 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use super::{ToHtml, doc_html};
+use crate::model::Macro;
+
+const MACRO_TEMPLATE: &str = r#"
+    <div class="macro">
+        <div class="macro-header">
+            <span class="macro-name">{name}!</span>
+            {{ if exported }}<span class="macro-exported">#[macro_export]</span>{{ endif }}
+        </div>
+        {{ if doc }}{doc | unescaped}{{ endif }}
+        <div class="macro-arms">
+            {{ for arm in arms }}
+                <div class="macro-arm">{arm}</div>
+            {{ endfor }}
+        </div>
+    </div>
+"#;
+
+#[derive(Serialize)]
+pub struct MacroContext {
+    pub name: String,
+    pub exported: bool,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    pub arms: Vec<String>,
+}
+
+impl From<&Macro> for MacroContext {
+    fn from(m: &Macro) -> Self {
+        Self {
+            name: m.name.clone(),
+            exported: m.exported,
+            doc: doc_html(m.doc.as_deref()),
+            arms: m.arms.clone(),
+        }
+    }
+}
+
+impl ToHtml for MacroContext {
+    fn to_html(&self) -> anyhow::Result<String> {
+        let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
+
+        tt.add_template("macro", MACRO_TEMPLATE)
+            .context("Failed to add template")?;
+
+        tt.render("macro", self)
+            .context("Failed to render template")
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use itertools::Itertools as _;
+use serde::Serialize;
+
+use super::{ToHtml as _, functions::FunctionContext};
+use crate::model::{Crate, Impl};
+
+/// Compartments of a struct or enum card with the items of its impl blocks, rendered
+/// with `{{ call members with members }}`
+pub const MEMBERS_TEMPLATE: &str = r#"
+    <div class="type-members">
+        {{ if implements }}
+            <div class="type-implements">
+                <span class="type-implements-label">«implements»</span>
+                {{ for trait_name in implements }}<span class="type-implemented-trait">{trait_name}</span>{{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if associated_items }}
+            <div class="impl-associated-items">
+                {{ for item in associated_items }}
+                    <div class="impl-associated-item">{item}</div>
+                {{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if methods }}
+            <div class="type-methods">
+                {{ for method in methods }}
+                    {method | unescaped}
+                {{ endfor }}
+            </div>
+        {{ endif }}
+    </div>
+"#;
+
+#[derive(Serialize, Default)]
+pub struct MembersContext {
+    /// The traits implemented for the type, as written in the impl blocks
+    pub implements: Vec<String>,
+    /// Associated types and consts of the inherent impl blocks
+    pub associated_items: Vec<String>,
+    /// Rendered `FunctionContext`s of the inherent impl blocks
+    pub methods: Vec<String>,
+}
+
+/// The impl blocks of the structs and enums of the crate by the full path of their type,
+/// matched through the resolved `self_path` so impls in other modules are found too
+pub struct TypeMembers<'a> {
+    impls: HashMap<String, Vec<&'a Impl>>,
+}
+
+impl<'a> TypeMembers<'a> {
+    pub fn new(krate: &'a Crate) -> Self {
+        let mut impls: HashMap<String, Vec<&'a Impl>> = krate
+            .root
+            .walk()
+            .flat_map(|module| {
+                module
+                    .structs
+                    .iter()
+                    .map(|s| &s.name)
+                    .chain(module.enums.iter().map(|e| &e.name))
+                    .map(|name| (module.item_path(name), Vec::new()))
+            })
+            .collect();
+        for imp in krate.root.walk().flat_map(|module| &module.impls) {
+            if let Some(list) = imp.self_path.as_ref().and_then(|path| impls.get_mut(path)) {
+                list.push(imp);
+            }
+        }
+
+        Self { impls }
+    }
+
+    /// Whether the impl block is shown on the card of its type instead of on its own:
+    /// inherent impls of the structs and enums of the crate
+    pub fn owns(&self, imp: &Impl) -> bool {
+        imp.trait_name.is_none()
+            && imp
+                .self_path
+                .as_ref()
+                .is_some_and(|path| self.impls.contains_key(path))
+    }
+
+    /// The members of the struct or enum at `path`, `None` if it has no impl blocks
+    pub fn context(&self, path: &str) -> anyhow::Result<Option<MembersContext>> {
+        let Some(impls) = self.impls.get(path).filter(|impls| !impls.is_empty()) else {
+            return Ok(None);
+        };
+        let (inherent, trait_impls): (Vec<&Impl>, Vec<&Impl>) =
+            impls.iter().partition(|imp| imp.trait_name.is_none());
+
+        Ok(Some(MembersContext {
+            implements: trait_impls
+                .iter()
+                .filter_map(|imp| imp.trait_name.clone())
+                .unique()
+                .collect(),
+            associated_items: inherent
+                .iter()
+                .flat_map(|imp| imp.associated_items())
+                .collect(),
+            methods: inherent
+                .iter()
+                .flat_map(|imp| &imp.functions)
+                .map(|f| FunctionContext::new(f).to_html())
+                .collect::<anyhow::Result<_>>()?,
+        }))
+    }
+}
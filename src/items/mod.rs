@@ -1,9 +1,74 @@
+pub mod declarations;
 pub mod enums;
+pub mod extern_blocks;
 pub mod functions;
 pub mod impl_blocks;
+pub mod macros;
+pub mod members;
 pub mod module;
 pub mod structs;
+pub mod traits;
+pub mod unions;
+
+use serde::Serialize;
+
+use crate::model::{abbreviate, summary};
 
 pub trait ToHtml {
     fn to_html(&self) -> anyhow::Result<String>;
 }
+
+/// A doc comment as HTML: its first paragraph as a description that expands to the
+/// rest of the comment, `None` for undocumented items
+pub fn doc_html(doc: Option<&str>) -> Option<String> {
+    let doc = doc?.trim();
+    let description = summary(Some(doc))?;
+    let more = doc
+        .split_once("\n\n")
+        .map(|(_, more)| more.trim())
+        .filter(|more| !more.is_empty());
+
+    let description = escape(&description);
+    Some(more.map_or_else(
+        || format!(r#"<div class="item-doc">{description}</div>"#),
+        |more| {
+            format!(
+                r#"<details class="item-doc"><summary>{description}</summary><div class="item-doc-more">{}</div></details>"#,
+                escape(more)
+            )
+        },
+    ))
+}
+
+/// A derived trait or an attribute shown as badge on a card
+#[derive(Serialize)]
+pub struct BadgeContext {
+    pub text: String,
+    /// The whole attribute, shown on hover
+    pub title: String,
+    /// `item-derive` or `item-attribute`
+    pub class: &'static str,
+}
+
+/// Badges for the derived traits followed by the other attributes of an item
+pub fn badges(derives: &[String], attributes: &[String]) -> Vec<BadgeContext> {
+    derives
+        .iter()
+        .map(|derive| BadgeContext {
+            text: derive.clone(),
+            title: format!("#[derive({derive})]"),
+            class: "item-derive",
+        })
+        .chain(attributes.iter().map(|attribute| BadgeContext {
+            text: format!("#[{}]", abbreviate(attribute)),
+            title: format!("#[{attribute}]"),
+            class: "item-attribute",
+        }))
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    tinytemplate::escape(text, &mut escaped);
+    escaped
+}
@@ -12,6 +12,7 @@ const MODULE_TEMPLATE: &str = r#"
             <span class="module-name">{name}</span>
         </label>
         <div class="module-contents">
+            {{ if doc }}{doc}{{ endif }}
 {contents}
         </div>
     </div>
@@ -20,6 +21,8 @@ const MODULE_TEMPLATE: &str = r#"
 #[derive(Serialize)]
 pub struct ModContext {
     pub name: String,
+    /// Rendered doc comment
+    pub doc: Option<String>,
     pub contents: String,
 }
 
@@ -2,47 +2,113 @@ use anyhow::Context as _;
 use serde::Serialize;
 use tinytemplate::TinyTemplate;
 
-use crate::items::ToHtml;
+use crate::{
+    items::{
+        BadgeContext, ToHtml, badges, doc_html,
+        members::{MEMBERS_TEMPLATE, MembersContext},
+    },
+    model::{Field, Struct, Visibility},
+};
 
 const STRUCT_TEMPLATE: &str = r#"
     <div class="struct">
         <div class="struct-name">{name}</div>
+        {{ if badges }}<div class="item-badges">{{ for badge in badges }}<span class="item-badge {badge.class}" title="{badge.title}">{badge.text}</span>{{ endfor }}</div>{{ endif }}
+        {{ if doc }}{doc | unescaped}{{ endif }}
         <div class="struct-public-fields">
             {{ for field in public_fields }}
-                <div class="struct-field">
-                    <div class="struct-field-name">{field.name}</div>
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
+                    <div class="struct-field-type">{field.type_}</div>
+                </div>
+            {{ endfor }}
+        </div>
+        <div class="struct-restricted-fields">
+            {{ for field in restricted_fields }}
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
                     <div class="struct-field-type">{field.type_}</div>
                 </div>
             {{ endfor }}
         </div>
         <div class="struct-private-fields">
             {{ for field in private_fields }}
-                <div class="struct-field">
-                    <div class="struct-field-name">{field.name}</div>
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
                     <div class="struct-field-type">{field.type_}</div>
                 </div>
             {{ endfor }}
         </div>
+        {{ if members }}{{ call members with members }}{{ endif }}
     </div>
 "#;
 
 #[derive(Serialize)]
 pub struct StructContext {
     pub name: String,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    pub badges: Vec<BadgeContext>,
     pub public_fields: Vec<StructFieldContext>,
+    /// `pub(crate)`, `pub(super)` and `pub(in path)` fields
+    pub restricted_fields: Vec<StructFieldContext>,
     pub private_fields: Vec<StructFieldContext>,
+    /// Items of the impl blocks when methods are grouped under their type
+    pub members: Option<MembersContext>,
 }
 
 #[derive(Serialize)]
 pub struct StructFieldContext {
     pub name: String,
     pub type_: String,
+    /// As written in Rust, or `private`
+    pub visibility: String,
+    /// The whole doc comment, shown on hover
+    pub doc: Option<String>,
+}
+
+impl From<&Struct> for StructContext {
+    fn from(s: &Struct) -> Self {
+        let [public_fields, restricted_fields, private_fields] = field_groups(&s.fields);
+
+        Self {
+            name: format!("{}{}", s.name, s.generics.as_deref().unwrap_or_default()),
+            doc: doc_html(s.doc.as_deref()),
+            badges: badges(&s.derives, &s.attributes),
+            public_fields,
+            restricted_fields,
+            private_fields,
+            members: None,
+        }
+    }
+}
+
+/// The public, restricted and private fields, each in declaration order
+pub fn field_groups(fields: &[Field]) -> [Vec<StructFieldContext>; 3] {
+    let mut groups: [Vec<StructFieldContext>; 3] = Default::default();
+    for (i, f) in fields.iter().enumerate() {
+        let group = match f.visibility {
+            Visibility::Public => 0,
+            Visibility::Crate | Visibility::Super | Visibility::InPath(_) => 1,
+            Visibility::Private => 2,
+        };
+        groups[group].push(StructFieldContext {
+            name: f.display_name(i),
+            type_: f.ty.clone(),
+            visibility: f.visibility.to_string(),
+            doc: f.doc.clone(),
+        });
+    }
+    groups
 }
 
 impl ToHtml for StructContext {
     fn to_html(&self) -> anyhow::Result<String> {
         let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
 
+        tt.add_template("members", MEMBERS_TEMPLATE)
+            .context("Failed to add template")?;
         tt.add_template("struct", STRUCT_TEMPLATE)
             .context("Failed to add template")?;
 
@@ -0,0 +1,88 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use super::{ToHtml, doc_html, functions::FunctionContext};
+use crate::model::{Function, Trait};
+
+const TRAIT_TEMPLATE: &str = r#"
+    <div class="trait">
+        <div class="trait-name">{name}{{ if supertraits }}<span class="trait-supertraits">{supertraits}</span>{{ endif }}</div>
+        {{ if doc }}{doc | unescaped}{{ endif }}
+        {{ if associated_items }}
+            <div class="trait-associated-items">
+                {{ for item in associated_items }}
+                    <div class="trait-associated-item">{item}</div>
+                {{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if required_methods }}
+            <div class="trait-methods trait-required-methods">
+                <div class="trait-methods-header">Required methods</div>
+                {{ for method in required_methods }}
+                    {method | unescaped}
+                {{ endfor }}
+            </div>
+        {{ endif }}
+        {{ if provided_methods }}
+            <div class="trait-methods trait-provided-methods">
+                <div class="trait-methods-header">Provided methods</div>
+                {{ for method in provided_methods }}
+                    {method | unescaped}
+                {{ endfor }}
+            </div>
+        {{ endif }}
+    </div>
+"#;
+
+#[derive(Serialize)]
+pub struct TraitContext {
+    pub name: String,
+    pub supertraits: Option<String>,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    /// Associated types and consts as declared
+    pub associated_items: Vec<String>,
+    /// Rendered `FunctionContext`s
+    pub required_methods: Vec<String>,
+    pub provided_methods: Vec<String>,
+}
+
+impl TraitContext {
+    /// Create a `TraitContext` from a model `Trait`, rendering its methods
+    pub fn new(t: &Trait) -> anyhow::Result<Self> {
+        let render = |methods: &[Function]| {
+            methods
+                .iter()
+                .map(|f| FunctionContext::new(f).to_html())
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            name: format!("{}{}", t.name, t.generics.as_deref().unwrap_or_default()),
+            supertraits: t.supertraits(),
+            doc: doc_html(t.doc.as_deref()),
+            associated_items: t
+                .types
+                .iter()
+                .map(ToString::to_string)
+                .chain(t.consts.iter().map(ToString::to_string))
+                .collect(),
+            required_methods: render(&t.required_methods)?,
+            provided_methods: render(&t.provided_methods)?,
+        })
+    }
+}
+
+impl ToHtml for TraitContext {
+    fn to_html(&self) -> anyhow::Result<String> {
+        let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
+
+        tt.add_template("trait", TRAIT_TEMPLATE)
+            .context("Failed to add template")?;
+
+        tt.render("trait", self)
+            .context("Failed to render template")
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use tinytemplate::TinyTemplate;
+
+use crate::{
+    items::{
+        ToHtml, doc_html,
+        structs::{StructFieldContext, field_groups},
+    },
+    model::Union,
+};
+
+const UNION_TEMPLATE: &str = r#"
+    <div class="struct union">
+        <div class="struct-name"><span class="union-keyword">union</span> {name}</div>
+        {{ if doc }}{doc | unescaped}{{ endif }}
+        <div class="struct-public-fields">
+            {{ for field in public_fields }}
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
+                    <div class="struct-field-type">{field.type_}</div>
+                </div>
+            {{ endfor }}
+        </div>
+        <div class="struct-restricted-fields">
+            {{ for field in restricted_fields }}
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
+                    <div class="struct-field-type">{field.type_}</div>
+                </div>
+            {{ endfor }}
+        </div>
+        <div class="struct-private-fields">
+            {{ for field in private_fields }}
+                <div class="struct-field" title="{field.visibility}">
+                    <div class="struct-field-name"{{ if field.doc }} title="{field.doc}"{{ endif }}>{field.name}</div>
+                    <div class="struct-field-type">{field.type_}</div>
+                </div>
+            {{ endfor }}
+        </div>
+    </div>
+"#;
+
+/// A union as a struct card marked with the `union` keyword
+#[derive(Serialize)]
+pub struct UnionContext {
+    pub name: String,
+    /// Rendered doc comment
+    pub doc: Option<String>,
+    pub public_fields: Vec<StructFieldContext>,
+    /// `pub(crate)`, `pub(super)` and `pub(in path)` fields
+    pub restricted_fields: Vec<StructFieldContext>,
+    pub private_fields: Vec<StructFieldContext>,
+}
+
+impl From<&Union> for UnionContext {
+    fn from(u: &Union) -> Self {
+        let [public_fields, restricted_fields, private_fields] = field_groups(&u.fields);
+
+        Self {
+            name: format!("{}{}", u.name, u.generics.as_deref().unwrap_or_default()),
+            doc: doc_html(u.doc.as_deref()),
+            public_fields,
+            restricted_fields,
+            private_fields,
+        }
+    }
+}
+
+impl ToHtml for UnionContext {
+    fn to_html(&self) -> anyhow::Result<String> {
+        let mut tt = TinyTemplate::new();
+        tt.add_formatter("unescaped", tinytemplate::format_unescaped);
+
+        tt.add_template("union", UNION_TEMPLATE)
+            .context("Failed to add template")?;
+
+        tt.render("union", self)
+            .context("Failed to render template")
+    }
+}
@@ -0,0 +1,346 @@
+//! Generate diagrams for a Rust project based on its source code.
+//!
+//! The [`Generator`] parses a crate into the format-neutral [`model`] and renders it
+//! into one of the supported output [`Format`]s:
+//!
+//! ```no_run
+//! use diagen::{Format, Generator};
+//!
+//! let generator = Generator::from_path("src/lib.rs")
+//!     .include_tests(true)
+//!     .format(Format::Html);
+//!
+//! let krate = generator.parse()?;
+//! println!("{} has {} modules", krate.name, krate.root.walk().count());
+//!
+//! let html = generator.render(&krate)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::Context as _;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::model::{Crate, VisibilityLevel};
+
+mod items;
+mod logic;
+pub mod model;
+mod render;
+#[cfg(test)]
+mod tests;
+
+pub use render::json::SCHEMA_VERSION as JSON_SCHEMA_VERSION;
+
+/// Pipe operator for functional-style programming
+trait Pipe<T> {
+    fn pipe<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(T) -> U;
+}
+
+impl<T> Pipe<T> for T {
+    fn pipe<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(T) -> U,
+    {
+        f(self)
+    }
+}
+
+/// Output format of a diagram
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[non_exhaustive]
+pub enum Format {
+    /// Standalone HTML page
+    #[default]
+    Html,
+    /// The parsed crate as a versioned JSON document
+    Json,
+    /// Mermaid class diagram
+    Mermaid,
+    /// `PlantUML` class diagram
+    #[cfg_attr(feature = "cli", value(name = "plantuml"))]
+    PlantUml,
+    /// Graphviz DOT graph
+    Dot,
+    /// Standalone SVG image laid out without external tools
+    Svg,
+    /// Markdown report of the modules and their items
+    Markdown,
+    /// D2 diagram
+    D2,
+    /// Editable draw.io (diagrams.net) file
+    #[cfg_attr(feature = "cli", value(name = "drawio"))]
+    DrawIo,
+    /// `GraphML` graph of the modules and items for graph analysis tools
+    #[cfg_attr(feature = "cli", value(name = "graphml"))]
+    GraphMl,
+    /// Structurizr DSL workspace with a C4 component view
+    Structurizr,
+    /// Excalidraw scene laid out without external tools
+    Excalidraw,
+    /// Standalone LaTeX document with a `TikZ` picture
+    Tikz,
+    /// `TikZ` picture to `\input` into a LaTeX document that loads `TikZ`
+    TikzFragment,
+}
+
+impl Format {
+    /// File extension used for output in this format
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Json => "json",
+            Self::Mermaid => "mmd",
+            Self::PlantUml => "puml",
+            Self::Dot => "dot",
+            Self::Svg => "svg",
+            Self::Markdown => "md",
+            Self::D2 => "d2",
+            Self::DrawIo => "drawio",
+            Self::GraphMl => "graphml",
+            Self::Structurizr => "dsl",
+            Self::Excalidraw => "excalidraw",
+            Self::Tikz | Self::TikzFragment => "tex",
+        }
+    }
+}
+
+/// Attributes shown on structs, enums, variants and functions unless configured otherwise
+pub const DEFAULT_ATTRIBUTES: [&str; 7] = [
+    "derive",
+    "non_exhaustive",
+    "repr",
+    "must_use",
+    "deprecated",
+    "default",
+    "serde",
+];
+
+/// Where the source code of the crate comes from
+#[derive(Debug, Clone)]
+pub enum Input {
+    /// `main.rs`, `lib.rs` or the root directory of the crate
+    Path(PathBuf),
+    /// The source code of a single file
+    Source(String),
+}
+
+/// Builder for parsing a crate and rendering its diagram
+#[derive(Debug, Clone)]
+pub struct Generator {
+    input: Input,
+    include_tests: bool,
+    min_visibility: VisibilityLevel,
+    attributes: Vec<String>,
+    group_methods: bool,
+    format: Format,
+    title: String,
+}
+
+impl Generator {
+    /// Create a generator for the crate at `path`, see [`Input::Path`]
+    #[must_use]
+    pub fn from_path<P: Into<PathBuf>>(path: P) -> Self {
+        Self::new(Input::Path(path.into()))
+    }
+
+    /// Create a generator for a single file of source code.
+    ///
+    /// Out-of-line modules (`mod foo;`) cannot be resolved without a path and fail to parse.
+    #[must_use]
+    pub fn from_source<S: Into<String>>(source: S) -> Self {
+        Self::new(Input::Source(source.into()))
+    }
+
+    /// Create a generator with the default options
+    #[must_use]
+    pub fn new(input: Input) -> Self {
+        Self {
+            input,
+            include_tests: false,
+            min_visibility: VisibilityLevel::default(),
+            attributes: DEFAULT_ATTRIBUTES.map(String::from).to_vec(),
+            group_methods: false,
+            format: Format::default(),
+            title: "Diagram".to_owned(),
+        }
+    }
+
+    /// Include test modules and test functions (excluded by default)
+    #[must_use]
+    pub const fn include_tests(mut self, include_tests: bool) -> Self {
+        self.include_tests = include_tests;
+        self
+    }
+
+    /// Only include items, fields and methods at least this visible (all by default)
+    #[must_use]
+    pub const fn min_visibility(mut self, min_visibility: VisibilityLevel) -> Self {
+        self.min_visibility = min_visibility;
+        self
+    }
+
+    /// Names of the attributes to keep on structs, enums, variants and functions, e.g. `repr` for
+    /// `#[repr(C)]` or `derive` for the derived traits ([`DEFAULT_ATTRIBUTES`] by default)
+    #[must_use]
+    pub fn attributes<I, S>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Show the methods of inherent impl blocks on the cards of their structs and enums
+    /// and list the implemented traits there (HTML only, off by default)
+    #[must_use]
+    pub const fn group_methods(mut self, group_methods: bool) -> Self {
+        self.group_methods = group_methods;
+        self
+    }
+
+    /// Set the output format (HTML by default)
+    #[must_use]
+    pub const fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the title of the diagram ("Diagram" by default)
+    #[must_use]
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Parse the crate into the format-neutral model
+    /// # Errors
+    /// Fails if the entry point cannot be found or a file cannot be read or parsed
+    pub fn parse(&self) -> anyhow::Result<Crate> {
+        let mut krate = match &self.input {
+            Input::Path(path) => {
+                let path = find_path(Some(path.clone()))?;
+                log::debug!("Using file: {}", path.display());
+
+                logic::parse_crate(&path, self.include_tests)
+                    .context(format!("Failed to parse file: {}", path.display()))?
+            }
+            Input::Source(source) => {
+                logic::parse_source(source, self.include_tests).context("Failed to parse source")?
+            }
+        };
+        logic::retain_visible(&mut krate, self.min_visibility);
+        logic::retain_attributes(&mut krate, &self.attributes);
+        Ok(krate)
+    }
+
+    /// Render an already parsed crate in the configured format
+    /// # Errors
+    /// Fails if the output could not be rendered
+    pub fn render(&self, krate: &Crate) -> anyhow::Result<String> {
+        match self.format {
+            Format::Html => render::html::render(krate, &self.title, self.group_methods),
+            Format::Json => render::json::render(krate),
+            Format::Mermaid => render::mermaid::render(krate),
+            Format::PlantUml => render::plantuml::render(krate),
+            Format::Dot => render::dot::render(krate),
+            Format::Svg => render::svg::render(krate),
+            Format::Markdown => render::markdown::render(krate),
+            Format::D2 => render::d2::render(krate),
+            Format::DrawIo => render::drawio::render(krate),
+            Format::GraphMl => render::graphml::render(krate),
+            Format::Structurizr => render::structurizr::render(krate),
+            Format::Excalidraw => render::excalidraw::render(krate),
+            Format::Tikz => render::tikz::render(krate),
+            Format::TikzFragment => render::tikz::render_fragment(krate),
+        }
+    }
+
+    /// Parse the crate and render it in the configured format
+    /// # Errors
+    /// Fails if the crate could not be parsed or rendered
+    pub fn generate(&self) -> anyhow::Result<String> {
+        self.render(&self.parse()?)
+    }
+}
+
+/// Returns the provided path or searches for main.rs or lib.rs in the current directory
+/// If provided path is a repository root, automatically finds the entry point
+/// # Errors
+/// Fails if no entry point can be found
+pub fn find_path(provided_path: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    let file_path = if let Some(path) = provided_path {
+        // Check if the provided path is a directory (repository root)
+        if path.is_dir() {
+            log::info!(
+                "Directory provided: {}, searching for entry point",
+                path.display()
+            );
+            find_entry_point_in_directory(&path)?
+        } else {
+            // It's already a file path
+            path
+        }
+    } else {
+        log::info!(
+            "No input file provided, searching for main.rs or lib.rs in the current directory"
+        );
+        let current_dir = env::current_dir().context("Failed to get current directory")?;
+        find_entry_point_in_directory(&current_dir)?
+    };
+
+    Ok(file_path)
+}
+
+/// Find the main entry point (main.rs or lib.rs) in a given directory
+fn find_entry_point_in_directory(dir: &Path) -> anyhow::Result<PathBuf> {
+    let mut search_dir = dir.to_path_buf();
+
+    // Check if this is a Cargo project root
+    if search_dir.join("Cargo.toml").exists() {
+        log::info!(
+            "Cargo.toml found in {}, assuming a Cargo project",
+            search_dir.display()
+        );
+        search_dir = search_dir.join("src");
+
+        // First try to find lib.rs (library crate)
+        let lib_path = search_dir.join("lib.rs");
+        if lib_path.exists() {
+            log::info!("Found lib.rs at {}", lib_path.display());
+            return Ok(lib_path);
+        }
+
+        // Then try main.rs (binary crate)
+        let main_path = search_dir.join("main.rs");
+        if main_path.exists() {
+            log::info!("Found main.rs at {}", main_path.display());
+            return Ok(main_path);
+        }
+    }
+
+    // If not a Cargo project or no lib.rs/main.rs in src/, search in the directory itself
+    search_dir
+        .read_dir()
+        .context(format!("Failed to read directory {}", search_dir.display()))?
+        .find(|entry| {
+            entry.as_ref().is_ok_and(|file_entry| {
+                let file_name = file_entry.file_name().to_string_lossy().to_string();
+                file_name == "lib.rs" || file_name == "main.rs"
+            })
+        })
+        .context(format!(
+            "Failed to find main.rs or lib.rs in {}",
+            search_dir.display()
+        ))?
+        .context("Failed to get directory entry")?
+        .path()
+        .pipe(Ok)
+}
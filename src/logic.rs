@@ -1,130 +1,99 @@
 use anyhow::Context;
-use itertools::Itertools;
-use std::{fs, path::Path};
-use syn::{Field, Item, Visibility, parse_file, spanned::Spanned};
-
-use crate::items::{
-    ToHtml as _,
-    enums::{EnumContext, EnumVariantContext},
-    functions::FunctionContext,
-    impl_blocks::ImplContext,
-    module::ModContext,
-    structs::{StructContext, StructFieldContext},
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
 };
+use syn::{Item, parse_file, spanned::Spanned, visit::Visit};
 
-pub(crate) fn parse_file_recursive<P: AsRef<Path>>(
-    path: P,
-    include_tests: bool,
-) -> anyhow::Result<String> {
-    let contents = fs::read_to_string(path.as_ref())
-        .context(format!("Failed to read file {}", path.as_ref().display()))?;
+use crate::model::{
+    AssociatedConst, AssociatedType, Const, Crate, Enum, ExternBlock, Field, ForeignStatic,
+    Function, Impl, Location, Macro, Module, Relation, RelationKind, Static, Struct, Trait,
+    TypeAlias, Union, Variant, Visibility, VisibilityLevel,
+};
 
-    let parsed_file = parse_file(contents.as_str())?;
-    let string_ret =
-        organize_and_render_items(path.as_ref(), parsed_file.items, include_tests, false)?;
+/// Parse the crate whose entry point is `path` into the format-neutral model
+pub fn parse_crate<P: AsRef<Path>>(path: P, include_tests: bool) -> anyhow::Result<Crate> {
+    let root = parse_file_recursive(path.as_ref(), include_tests)?;
+
+    Ok(link_crate(crate_name(path.as_ref()), root))
+}
+
+/// Parse the source code of a single file into the format-neutral model
+pub fn parse_source(source: &str, include_tests: bool) -> anyhow::Result<Crate> {
+    let parsed_file = parse_file(source)?;
+    let mut root = build_module(
+        Path::new(""),
+        "crate",
+        "crate",
+        parsed_file.items,
+        include_tests,
+        false,
+    )?;
+    root.doc = doc(&parsed_file.attrs);
 
-    Ok(string_ret)
+    Ok(link_crate("crate".to_owned(), root))
+}
+
+pub fn parse_file_recursive<P: AsRef<Path>>(
+    path: P,
+    include_tests: bool,
+) -> anyhow::Result<Module> {
+    parse_file_recursive_with_context(path, "crate", "crate", include_tests, false)
 }
 
 /// Helper function for parsing files with test context
 fn parse_file_recursive_with_context<P: AsRef<Path>>(
     path: P,
+    name: &str,
+    module_path: &str,
     include_tests: bool,
     in_test_context: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<Module> {
     let contents = fs::read_to_string(path.as_ref())
         .context(format!("Failed to read file {}", path.as_ref().display()))?;
 
     let parsed_file = parse_file(contents.as_str())?;
-    let string_ret = organize_and_render_items(
+    let mut module = build_module(
         path.as_ref(),
+        name,
+        module_path,
         parsed_file.items,
         include_tests,
         in_test_context,
     )?;
+    module.doc = doc(&parsed_file.attrs);
 
-    Ok(string_ret)
+    Ok(module)
 }
 
-/// Organize items by type and render them in a structured way
-pub(crate) fn organize_and_render_items<P: AsRef<Path>>(
+/// Collect the items of a module into the model
+pub fn build_module<P: AsRef<Path>>(
     path: P,
+    name: &str,
+    module_path: &str,
     items: Vec<Item>,
     include_tests: bool,
     in_test_context: bool,
-) -> anyhow::Result<String> {
-    let mut structs = Vec::new();
-    let mut enums = Vec::new();
-    let mut functions = Vec::new();
-    let mut impls = Vec::new();
-    let mut modules = Vec::new();
-    let mut other_items = Vec::new();
-
-    // Group items by type
-    for item in items {
-        match &item {
-            Item::Struct(_) => structs.push(item),
-            Item::Enum(_) => enums.push(item),
-            Item::Fn(_) => functions.push(item),
-            Item::Impl(_) => impls.push(item),
-            Item::Mod(_) => modules.push(item),
-            Item::Const(_)
-            | Item::ExternCrate(_)
-            | Item::ForeignMod(_)
-            | Item::Macro(_)
-            | Item::Static(_)
-            | Item::Trait(_)
-            | Item::TraitAlias(_)
-            | Item::Type(_)
-            | Item::Union(_)
-            | Item::Use(_)
-            | Item::Verbatim(_)
-            | _ => other_items.push(item),
-        }
-    }
-
-    let mut result = String::new();
-
-    // Helper function to create a section with items
-    let create_section =
-        |section_name: &str, items: Vec<Item>, grid_class: &str| -> anyhow::Result<String> {
-            if items.is_empty() {
-                return Ok(String::new());
-            }
+) -> anyhow::Result<Module> {
+    let mut module = Module {
+        name: name.to_owned(),
+        path: module_path.to_owned(),
+        file: Some(path.as_ref().to_path_buf()).filter(|file| !file.as_os_str().is_empty()),
+        ..Module::default()
+    };
 
-            let mut section_html = format!(
-                r#"<div class="item-section">
-    <div class="item-section-header">{section_name}</div>
-    <div class="{grid_class}">
-"#
-            );
-
-            for item in items {
-                let rendered = traverse_ast(path.as_ref(), item, include_tests, in_test_context)?;
-                section_html.push_str(&rendered);
-            }
-
-            section_html.push_str("    </div>\n</div>\n\n");
-            Ok(section_html)
-        };
-
-    // Render sections in organized order
-    result.push_str(&create_section("Structs", structs, "structs-grid")?);
-    result.push_str(&create_section("Enums", enums, "enums-grid")?);
-    result.push_str(&create_section("Functions", functions, "functions-grid")?);
-    result.push_str(&create_section(
-        "Implementations",
-        impls,
-        "impl-blocks-grid",
-    )?);
-
-    // Modules get special treatment - they don't need a section wrapper
-    for item in modules.into_iter().chain(other_items) {
-        let rendered = traverse_ast(path.as_ref(), item, include_tests, in_test_context)?;
-        result.push_str(&rendered);
+    for item in items {
+        traverse_ast(
+            path.as_ref(),
+            &mut module,
+            item,
+            include_tests,
+            in_test_context,
+        )?;
     }
 
-    Ok(result)
+    Ok(module)
 }
 
 /// Check if an attribute list contains test-related attributes
@@ -194,219 +163,126 @@ fn should_exclude_from_tests(
 )]
 fn traverse_ast<P: AsRef<Path>>(
     path: P,
+    module: &mut Module,
     ast: Item,
     include_tests: bool,
     in_test_context: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<()> {
     match ast {
         Item::Impl(imp) => {
-            let functions: Vec<String> = imp
-                .items
-                .into_iter()
-                .filter_map(|item| match item {
-                    syn::ImplItem::Fn(f) => {
-                        if should_exclude_from_tests(&f.attrs, in_test_context, include_tests) {
-                            None
-                        } else {
-                            let context = FunctionContext::new(&f.sig, &f.vis);
-                            Some(context.to_html())
-                        }
-                    }
-                    syn::ImplItem::Const(_)
-                    | syn::ImplItem::Type(_)
-                    | syn::ImplItem::Macro(_)
-                    | syn::ImplItem::Verbatim(_)
-                    | _ => None,
-                })
-                .filter_map(anyhow::Result::ok)
-                .collect();
-
-            // Only create impl block if it has functions to display
-            if functions.is_empty() {
-                return Ok(String::new());
+            // Check if this impl block should be excluded from tests
+            if should_exclude_from_tests(&imp.attrs, in_test_context, include_tests) {
+                return Ok(());
             }
 
-            let target_type = imp
-                .self_ty
-                .span()
-                .source_text()
-                .expect("Could not get source_text");
-
-            let trait_name = imp.trait_.as_ref().map(|(_, path, _)| {
-                path.span()
-                    .source_text()
-                    .expect("Could not get source_text")
-            });
-
-            let generics = if imp.generics.params.is_empty() {
-                None
-            } else {
-                Some(
-                    imp.generics
-                        .span()
-                        .source_text()
-                        .expect("Could not get source_text"),
-                )
-            };
-
-            let context = ImplContext {
-                target_type,
-                trait_name,
-                generics,
-                functions,
-            };
-
-            Ok(context.to_html()?)
+            module
+                .impls
+                .push(build_impl(&imp, in_test_context, include_tests));
         }
         Item::Struct(s) => {
             // Check if this struct should be excluded from tests
             if should_exclude_from_tests(&s.attrs, in_test_context, include_tests) {
-                return Ok(String::new());
+                return Ok(());
             }
 
-            let (public, private) = s
-                .fields
-                .clone()
-                .into_iter()
-                .enumerate()
-                .partition::<Vec<(usize, Field)>, _>(|(_, f)| {
-                    matches!(f.vis, Visibility::Public(_))
-                });
-
-            let public_fields = public
-                .into_iter()
-                .map(|(i, f)| StructFieldContext {
-                    name: f
-                        .ident
-                        .as_ref()
-                        .map_or_else(|| format!("{i}"), ToString::to_string),
-                    type_: f
-                        .ty
-                        .span()
-                        .source_text()
-                        .expect("Could not get source_text"),
-                })
-                .collect();
-
-            let private_fields = private
-                .into_iter()
-                .map(|(i, f)| StructFieldContext {
-                    name: f
-                        .ident
-                        .as_ref()
-                        .map_or_else(|| format!("{i}"), ToString::to_string),
-                    type_: f
-                        .ty
-                        .span()
-                        .source_text()
-                        .expect("Could not get source_text"),
-                })
-                .collect();
-
-            let context = StructContext {
-                name: format!(
-                    "{}{}",
-                    s.ident,
-                    s.generics.span().source_text().unwrap_or_default()
-                ),
-                public_fields,
-                private_fields,
-            };
-
-            Ok(context.to_html()?)
+            module.structs.push(Struct {
+                name: s.ident.to_string(),
+                generics: generics(&s.generics),
+                visibility: visibility(&s.vis),
+                fields: fields(&s.fields),
+                location: location(s.ident.span()),
+                doc: doc(&s.attrs),
+                derives: derives(&s.attrs),
+                attributes: attributes(&s.attrs),
+            });
         }
         Item::Enum(e) => {
             // Check if this enum should be excluded from tests
             if should_exclude_from_tests(&e.attrs, in_test_context, include_tests) {
-                return Ok(String::new());
+                return Ok(());
             }
 
-            let variants = e
-                .variants
-                .into_iter()
-                .map(|v| {
-                    let data = match &v.fields {
-                        syn::Fields::Named(fields) => {
-                            let field_list = fields
-                                .named
-                                .iter()
-                                .map(|f| {
-                                    format!(
-                                        "{}: {}",
-                                        f.ident.as_ref().expect("Named field should have ident"),
-                                        f.ty.span()
-                                            .source_text()
-                                            .expect("Could not get source_text")
-                                    )
-                                })
-                                .join(", ");
-                            Some(format!("{{ {field_list} }}"))
-                        }
-                        syn::Fields::Unnamed(fields) => {
-                            let field_list = fields
-                                .unnamed
-                                .iter()
-                                .map(|f| {
-                                    f.ty.span()
-                                        .source_text()
-                                        .expect("Could not get source_text")
-                                })
-                                .join(", ");
-                            Some(format!("({field_list})"))
-                        }
-                        syn::Fields::Unit => None,
-                    };
-
-                    EnumVariantContext {
+            module.enums.push(Enum {
+                name: e.ident.to_string(),
+                generics: generics(&e.generics),
+                visibility: visibility(&e.vis),
+                location: location(e.ident.span()),
+                doc: doc(&e.attrs),
+                derives: derives(&e.attrs),
+                attributes: attributes(&e.attrs),
+                variants: e
+                    .variants
+                    .iter()
+                    .map(|v| Variant {
                         name: v.ident.to_string(),
-                        data,
-                    }
-                })
-                .collect();
-
-            let context = EnumContext {
-                name: format!(
-                    "{}{}",
-                    e.ident,
-                    e.generics.span().source_text().unwrap_or_default()
-                ),
-                variants,
-            };
+                        // Variant fields are as visible as their enum
+                        fields: fields(&v.fields)
+                            .into_iter()
+                            .map(|f| Field {
+                                visibility: visibility(&e.vis),
+                                ..f
+                            })
+                            .collect(),
+                        discriminant: v.discriminant.as_ref().map(|(_, expr)| source_text(expr)),
+                        doc: doc(&v.attrs),
+                        attributes: attributes(&v.attrs),
+                    })
+                    .collect(),
+            });
+        }
+        Item::Trait(t) => {
+            if should_exclude_from_tests(&t.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
 
-            Ok(context.to_html()?)
+            module
+                .traits
+                .push(build_trait(&t, in_test_context, include_tests));
         }
         Item::Fn(f) => {
             // Check if this function should be excluded from tests
             if should_exclude_from_tests(&f.attrs, in_test_context, include_tests) {
-                return Ok(String::new());
+                return Ok(());
             }
 
-            let context = FunctionContext::new(&f.sig, &f.vis);
-            Ok(context.to_html()?)
+            module.functions.push(function(&f.sig, &f.vis, &f.attrs));
         }
         Item::Mod(m) => {
             // Check if this module should be excluded (test modules)
             if !include_tests && is_test_module(&m) {
-                return Ok(String::new());
+                return Ok(());
             }
 
             // Determine if we're entering a test context
             let entering_test_context = in_test_context || is_test_module(&m);
 
-            let contents: String = if let Some((_, items)) = m.content {
-                // Use the same organized approach for module contents
-                organize_and_render_items(
+            let name = m.ident.to_string();
+            let module_path = module.item_path(&name);
+            // Inline modules carry their `//!` docs in `m.attrs`, files in the parsed file
+            let (inner_attrs, outer_attrs): (Vec<_>, Vec<_>) = m
+                .attrs
+                .into_iter()
+                .partition(|attr| matches!(attr.style, syn::AttrStyle::Inner(_)));
+
+            let mut submodule = if let Some((_, items)) = m.content {
+                let mut submodule = build_module(
                     path.as_ref(),
+                    &name,
+                    &module_path,
                     items,
                     include_tests,
                     entering_test_context,
-                )?
+                )?;
+                submodule.doc = doc(&inner_attrs);
+                submodule
             } else {
                 parse_file_recursive_with_context(
                     path.as_ref()
                         .parent()
                         .context("Failed to get parent")?
-                        .join(format!("{}.rs", m.ident)),
+                        .join(format!("{name}.rs")),
+                    &name,
+                    &module_path,
                     include_tests,
                     entering_test_context,
                 )
@@ -414,35 +290,960 @@ fn traverse_ast<P: AsRef<Path>>(
                     path.as_ref()
                         .parent()
                         .context("Failed to get parent")?
-                        .join(format!("{}/mod.rs", m.ident)),
+                        .join(format!("{name}/mod.rs")),
+                    &name,
+                    &module_path,
                     include_tests,
                     entering_test_context,
                 ))
                 .context("Failed to parse mod")?
             };
+            submodule.visibility = visibility(&m.vis);
+            submodule.doc = match (doc(&outer_attrs), submodule.doc.take()) {
+                (Some(outer), Some(inner)) => Some(format!("{outer}\n\n{inner}")),
+                (outer, inner) => outer.or(inner),
+            };
+
+            module.modules.push(submodule);
+        }
+        Item::Use(u) => {
+            use_paths(&u.tree, "", &mut module.imports);
+        }
+        Item::Type(t) => {
+            if should_exclude_from_tests(&t.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
 
-            // Don't render empty modules
-            if contents.trim().is_empty() {
-                return Ok(String::new());
+            module.type_aliases.push(TypeAlias {
+                name: t.ident.to_string(),
+                generics: generics(&t.generics),
+                visibility: visibility(&t.vis),
+                location: location(t.ident.span()),
+                doc: doc(&t.attrs),
+                ty: source_text(&t.ty),
+            });
+        }
+        Item::Const(c) => {
+            if should_exclude_from_tests(&c.attrs, in_test_context, include_tests) {
+                return Ok(());
             }
 
-            let mod_context = ModContext {
-                name: m.ident.to_string(),
-                contents,
-            };
-            Ok(mod_context.to_html()?)
-        }
-        Item::Const(_)
-        | Item::ExternCrate(_)
-        | Item::ForeignMod(_)
-        | Item::Macro(_)
-        | Item::Static(_)
-        | Item::Trait(_)
-        | Item::TraitAlias(_)
-        | Item::Type(_)
-        | Item::Union(_)
-        | Item::Use(_)
-        | Item::Verbatim(_)
-        | _ => Ok(String::new()),
+            module.consts.push(Const {
+                name: c.ident.to_string(),
+                visibility: visibility(&c.vis),
+                location: location(c.ident.span()),
+                doc: doc(&c.attrs),
+                ty: source_text(&c.ty),
+                value: source_text(&c.expr),
+            });
+        }
+        Item::Static(s) => {
+            if should_exclude_from_tests(&s.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
+
+            module.statics.push(Static {
+                name: s.ident.to_string(),
+                visibility: visibility(&s.vis),
+                location: location(s.ident.span()),
+                doc: doc(&s.attrs),
+                is_mut: matches!(s.mutability, syn::StaticMutability::Mut(_)),
+                ty: source_text(&s.ty),
+                value: source_text(&s.expr),
+            });
+        }
+        Item::Union(u) => {
+            if should_exclude_from_tests(&u.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
+
+            module.unions.push(Union {
+                name: u.ident.to_string(),
+                generics: generics(&u.generics),
+                visibility: visibility(&u.vis),
+                location: location(u.ident.span()),
+                doc: doc(&u.attrs),
+                fields: u.fields.named.iter().map(field).collect(),
+            });
+        }
+        Item::ForeignMod(block) => {
+            if should_exclude_from_tests(&block.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
+
+            module
+                .extern_blocks
+                .push(build_extern_block(&block, in_test_context, include_tests));
+        }
+        Item::Macro(m) => {
+            if should_exclude_from_tests(&m.attrs, in_test_context, include_tests) {
+                return Ok(());
+            }
+
+            if let Some(name) = &m.ident
+                && m.mac.path.is_ident("macro_rules")
+            {
+                module.macros.push(Macro {
+                    name: name.to_string(),
+                    exported: m
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident("macro_export")),
+                    location: location(name.span()),
+                    doc: doc(&m.attrs),
+                    arms: macro_arms(m.mac.tokens),
+                });
+            }
+        }
+        Item::ExternCrate(_) | Item::TraitAlias(_) | Item::Verbatim(_) | _ => {}
+    }
+
+    Ok(())
+}
+
+/// Collect an impl block with the items that are not excluded as tests; the implementing
+/// type and trait are resolved once the whole crate is parsed
+fn build_impl(imp: &syn::ItemImpl, in_test_context: bool, include_tests: bool) -> Impl {
+    let mut model = Impl {
+        self_ty: source_text(&imp.self_ty),
+        self_path: None,
+        trait_name: imp.trait_.as_ref().map(|(_, path, _)| source_text(path)),
+        trait_path: None,
+        generics: generics(&imp.generics),
+        where_clause: where_clause(&imp.generics),
+        types: Vec::new(),
+        consts: Vec::new(),
+        functions: Vec::new(),
+        location: location(imp.impl_token.span),
+    };
+
+    for item in &imp.items {
+        match item {
+            syn::ImplItem::Fn(f) => {
+                if !should_exclude_from_tests(&f.attrs, in_test_context, include_tests) {
+                    model.functions.push(function(&f.sig, &f.vis, &f.attrs));
+                }
+            }
+            syn::ImplItem::Type(ty) => {
+                if !should_exclude_from_tests(&ty.attrs, in_test_context, include_tests) {
+                    model.types.push(AssociatedType {
+                        name: ty.ident.to_string(),
+                        bounds: Vec::new(),
+                        ty: Some(source_text(&ty.ty)),
+                    });
+                }
+            }
+            syn::ImplItem::Const(c) => {
+                if !should_exclude_from_tests(&c.attrs, in_test_context, include_tests) {
+                    model.consts.push(AssociatedConst {
+                        name: c.ident.to_string(),
+                        ty: source_text(&c.ty),
+                        value: Some(source_text(&c.expr)),
+                    });
+                }
+            }
+            syn::ImplItem::Macro(_) | syn::ImplItem::Verbatim(_) | _ => {}
+        }
+    }
+
+    model
+}
+
+/// Collect a trait with its associated items; methods take the visibility of the trait
+fn build_trait(t: &syn::ItemTrait, in_test_context: bool, include_tests: bool) -> Trait {
+    let mut model = Trait {
+        name: t.ident.to_string(),
+        generics: generics(&t.generics),
+        visibility: visibility(&t.vis),
+        location: location(t.ident.span()),
+        doc: doc(&t.attrs),
+        supertraits: t.supertraits.iter().map(source_text).collect(),
+        types: Vec::new(),
+        consts: Vec::new(),
+        required_methods: Vec::new(),
+        provided_methods: Vec::new(),
+    };
+
+    for item in &t.items {
+        match item {
+            syn::TraitItem::Fn(f) => {
+                if should_exclude_from_tests(&f.attrs, in_test_context, include_tests) {
+                    continue;
+                }
+                let method = function(&f.sig, &t.vis, &f.attrs);
+                if f.default.is_some() {
+                    model.provided_methods.push(method);
+                } else {
+                    model.required_methods.push(method);
+                }
+            }
+            syn::TraitItem::Type(ty) => model.types.push(AssociatedType {
+                name: ty.ident.to_string(),
+                bounds: ty.bounds.iter().map(source_text).collect(),
+                ty: ty.default.as_ref().map(|(_, ty)| source_text(ty)),
+            }),
+            syn::TraitItem::Const(c) => model.consts.push(AssociatedConst {
+                name: c.ident.to_string(),
+                ty: source_text(&c.ty),
+                value: c.default.as_ref().map(|(_, value)| source_text(value)),
+            }),
+            syn::TraitItem::Macro(_) | syn::TraitItem::Verbatim(_) | _ => {}
+        }
     }
+
+    model
+}
+
+/// The ABI string of `extern "ABI"`, `"C"` for a bare `extern`
+fn abi(abi: &syn::Abi) -> String {
+    abi.name
+        .as_ref()
+        .map_or_else(|| "C".to_owned(), syn::LitStr::value)
+}
+
+/// Collect the foreign items of an `extern` block that are not excluded as tests
+fn build_extern_block(
+    block: &syn::ItemForeignMod,
+    in_test_context: bool,
+    include_tests: bool,
+) -> ExternBlock {
+    let mut model = ExternBlock {
+        abi: abi(&block.abi),
+        location: location(block.abi.extern_token.span),
+        functions: Vec::new(),
+        statics: Vec::new(),
+        types: Vec::new(),
+    };
+
+    for item in &block.items {
+        match item {
+            syn::ForeignItem::Fn(f) => {
+                if !should_exclude_from_tests(&f.attrs, in_test_context, include_tests) {
+                    model.functions.push(function(&f.sig, &f.vis, &f.attrs));
+                }
+            }
+            syn::ForeignItem::Static(s) => {
+                if !should_exclude_from_tests(&s.attrs, in_test_context, include_tests) {
+                    model.statics.push(ForeignStatic {
+                        name: s.ident.to_string(),
+                        visibility: visibility(&s.vis),
+                        is_mut: matches!(s.mutability, syn::StaticMutability::Mut(_)),
+                        ty: source_text(&s.ty),
+                    });
+                }
+            }
+            syn::ForeignItem::Type(ty) => {
+                if !should_exclude_from_tests(&ty.attrs, in_test_context, include_tests) {
+                    model.types.push(ty.ident.to_string());
+                }
+            }
+            syn::ForeignItem::Macro(_) | syn::ForeignItem::Verbatim(_) | _ => {}
+        }
+    }
+
+    model
+}
+
+/// The matchers of the `(matcher) => { transcriber };` arms of a `macro_rules!` body,
+/// each on a single line
+fn macro_arms(tokens: proc_macro2::TokenStream) -> Vec<String> {
+    use proc_macro2::TokenTree;
+
+    let is_punct =
+        |c: char| move |token: &TokenTree| matches!(token, TokenTree::Punct(p) if p.as_char() == c);
+
+    let mut arms = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let TokenTree::Group(matcher) = token else {
+            continue;
+        };
+        // `=>` is lexed as `=` followed by `>`
+        if tokens.next_if(is_punct('=')).is_none() || tokens.next_if(is_punct('>')).is_none() {
+            continue;
+        }
+        // Skip the transcriber
+        tokens.next();
+
+        let text = matcher
+            .span()
+            .source_text()
+            .unwrap_or_else(|| matcher.to_string());
+        arms.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    arms
+}
+
+/// The source code a syntax node was parsed from
+fn source_text<T: Spanned>(node: &T) -> String {
+    node.span()
+        .source_text()
+        .expect("Could not get source_text")
+}
+
+/// Where a span starts in its file
+fn location(span: proc_macro2::Span) -> Location {
+    let start = span.start();
+    Location {
+        line: start.line,
+        column: start.column + 1,
+    }
+}
+
+/// The generic parameters as written in the source, `None` if there are none
+fn generics(generics: &syn::Generics) -> Option<String> {
+    if generics.params.is_empty() {
+        None
+    } else {
+        generics.span().source_text()
+    }
+}
+
+/// The predicates of the `where` clause
+fn where_clause(generics: &syn::Generics) -> Vec<String> {
+    generics
+        .where_clause
+        .iter()
+        .flat_map(|clause| &clause.predicates)
+        .map(source_text)
+        .collect()
+}
+
+/// The traits named in the `#[derive(...)]` attributes
+fn derives(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .flatten()
+        .map(|path| source_text(&path))
+        .collect()
+}
+
+/// The outer attributes other than doc comments and derives, e.g. `repr(C)`
+fn attributes(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.style == syn::AttrStyle::Outer)
+        .filter(|attr| !attr.path().is_ident("doc") && !attr.path().is_ident("derive"))
+        .map(|attr| source_text(&attr.meta))
+        .collect()
+}
+
+/// The path of an attribute as written, e.g. `repr` for `repr(C)`
+fn attribute_name(attribute: &str) -> &str {
+    attribute
+        .split(['(', '=', '['])
+        .next()
+        .unwrap_or_default()
+        .trim()
+}
+
+/// The text of the `///` or `//!` doc comments, `None` if there are none
+fn doc(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(text),
+                        ..
+                    }),
+                ..
+            }) => Some(text.value()),
+            syn::Meta::Path(_) | syn::Meta::List(_) | syn::Meta::NameValue(_) => None,
+        })
+        .flat_map(|text| {
+            text.split('\n')
+                .map(|line| line.strip_prefix(' ').unwrap_or(line).to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let doc = lines.join("\n").trim().to_owned();
+    if doc.is_empty() { None } else { Some(doc) }
+}
+
+/// Flatten a `use` tree into the paths it imports, e.g. `super::model::*`, followed by
+/// ` as alias` when renamed
+fn use_paths(tree: &syn::UseTree, prefix: &str, paths: &mut Vec<String>) {
+    let join = |name: &dyn std::fmt::Display| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}::{name}")
+        }
+    };
+    match tree {
+        syn::UseTree::Path(path) => use_paths(&path.tree, &join(&path.ident), paths),
+        syn::UseTree::Name(name) => paths.push(join(&name.ident)),
+        syn::UseTree::Rename(rename) => {
+            paths.push(format!("{} as {}", join(&rename.ident), rename.rename));
+        }
+        syn::UseTree::Glob(_) => paths.push(join(&"*")),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                use_paths(tree, prefix, paths);
+            }
+        }
+    }
+}
+
+/// The visibility of an item; `pub(in crate)` is `pub(crate)` and `pub(self)` is private
+fn visibility(vis: &syn::Visibility) -> Visibility {
+    match vis {
+        syn::Visibility::Public(_) => Visibility::Public,
+        syn::Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => {
+            Visibility::Crate
+        }
+        syn::Visibility::Restricted(restricted) if restricted.path.is_ident("super") => {
+            Visibility::Super
+        }
+        syn::Visibility::Restricted(restricted) if restricted.path.is_ident("self") => {
+            Visibility::Private
+        }
+        syn::Visibility::Restricted(restricted) => {
+            Visibility::InPath(source_text(&restricted.path))
+        }
+        syn::Visibility::Inherited => Visibility::Private,
+    }
+}
+
+fn fields(fields: &syn::Fields) -> Vec<Field> {
+    fields.iter().map(field).collect()
+}
+
+fn field(f: &syn::Field) -> Field {
+    Field {
+        name: f.ident.as_ref().map(ToString::to_string),
+        ty: source_text(&f.ty),
+        visibility: visibility(&f.vis),
+        doc: doc(&f.attrs),
+    }
+}
+
+/// Create a `Function` from a `syn::Signature` and its visibility
+fn function(sig: &syn::Signature, vis: &syn::Visibility, attrs: &[syn::Attribute]) -> Function {
+    Function {
+        name: sig.ident.to_string(),
+        generics: generics(&sig.generics),
+        visibility: visibility(vis),
+        is_async: sig.asyncness.is_some(),
+        is_const: sig.constness.is_some(),
+        is_unsafe: sig.unsafety.is_some(),
+        abi: sig.abi.as_ref().map(abi),
+        params: sig.inputs.iter().map(source_text).collect(),
+        return_type: match &sig.output {
+            syn::ReturnType::Default => None,
+            syn::ReturnType::Type(_, ty) => Some(source_text(ty)),
+        },
+        where_clause: where_clause(&sig.generics),
+        location: location(sig.ident.span()),
+        doc: doc(attrs),
+        attributes: attributes(attrs),
+    }
+}
+
+/// Name of the crate: the directory containing `src`, or the file stem of the entry point
+fn crate_name(path: &Path) -> String {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    path.parent()
+        .filter(|dir| dir.ends_with("src"))
+        .and_then(Path::parent)
+        .and_then(Path::file_name)
+        .or_else(|| path.file_stem())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Collects every path in a type as its segments, e.g. `Vec` and `model::Field` for
+/// `Vec<model::Field>`
+#[derive(Default)]
+struct TypePaths(Vec<Vec<String>>);
+
+impl<'ast> Visit<'ast> for TypePaths {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        self.0.push(path_segments(path));
+        syn::visit::visit_path(self, path);
+    }
+}
+
+/// The identifiers of the segments of a path, without generic arguments
+fn path_segments(path: &syn::Path) -> Vec<String> {
+    path.segments.iter().map(|s| s.ident.to_string()).collect()
+}
+
+/// Paths of the types a type expression refers to
+fn type_paths(ty: &str) -> Vec<Vec<String>> {
+    let mut paths = TypePaths::default();
+    if let Ok(ty) = syn::parse_str::<syn::Type>(ty) {
+        paths.visit_type(&ty);
+    }
+    paths.0
+}
+
+/// The types and modules of the crate, for resolving the paths written in a module
+struct TypeIndex {
+    /// Full paths of the types by name
+    by_name: HashMap<String, Vec<String>>,
+    types: HashSet<String>,
+    modules: HashSet<String>,
+}
+
+impl TypeIndex {
+    fn new(root: &Module) -> Self {
+        let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+        for module in root.walk() {
+            for name in module.type_names() {
+                by_name
+                    .entry(name.to_owned())
+                    .or_default()
+                    .push(module.item_path(name));
+            }
+        }
+        let types = by_name.values().flatten().cloned().collect();
+        let modules = root.walk().map(|m| m.path.clone()).collect();
+
+        Self {
+            by_name,
+            types,
+            modules,
+        }
+    }
+
+    /// Resolve a path written in `module` to a type of the crate.
+    ///
+    /// A single name is looked up in the module, then in its imports and glob imports and
+    /// last as the only type of the crate with that name. A qualified path is followed from
+    /// `crate`, `self`, `super`, a submodule or an imported module; paths starting at an
+    /// unknown module fall back to the name alone. Ambiguous paths, paths into the
+    /// standard library and names imported from other crates are not resolved.
+    fn resolve(&self, module: &Module, path: &[String]) -> Option<String> {
+        match path {
+            [] => None,
+            [name] => self.resolve_name(module, name),
+            [first, ..]
+                if matches!(first.as_str(), "std" | "core" | "alloc")
+                    || matches!(module.imported_names.get(first), Some(None)) =>
+            {
+                None
+            }
+            [first, rest @ .., name] => {
+                let Some(mut base) = self.module_base(module, first) else {
+                    return self.unique(name);
+                };
+                for segment in rest {
+                    if segment == "super" {
+                        base.truncate(base.rfind("::")?);
+                    } else {
+                        base = format!("{base}::{segment}");
+                    }
+                }
+                let path = format!("{base}::{name}");
+                self.types.contains(&path).then_some(path)
+            }
+        }
+    }
+
+    fn resolve_name(&self, module: &Module, name: &str) -> Option<String> {
+        let local = module.item_path(name);
+        if self.types.contains(&local) {
+            return Some(local);
+        }
+
+        match module.imported_names.get(name) {
+            // Imported from another crate
+            Some(None) => return None,
+            Some(Some(path)) if self.types.contains(path) => return Some(path.clone()),
+            _ => {}
+        }
+
+        module
+            .imports
+            .iter()
+            .filter_map(|import| import.strip_suffix("::*"))
+            .map(|base| format!("{base}::{name}"))
+            .find(|path| self.types.contains(path))
+            .or_else(|| self.unique(name))
+    }
+
+    /// The only type of the crate named `name`
+    fn unique(&self, name: &str) -> Option<String> {
+        match self.by_name.get(name)?.as_slice() {
+            [only] => Some(only.clone()),
+            _ => None,
+        }
+    }
+
+    /// The module the first segment of a qualified path refers to
+    fn module_base(&self, module: &Module, first: &str) -> Option<String> {
+        let base = match first {
+            "crate" => "crate".to_owned(),
+            "self" => module.path.clone(),
+            "super" => module.path.rsplit_once("::")?.0.to_owned(),
+            name => {
+                let submodule = format!("{}::{name}", module.path);
+                if self.modules.contains(&submodule) {
+                    submodule
+                } else {
+                    module
+                        .imported_names
+                        .get(name)?
+                        .clone()
+                        .filter(|path| self.modules.contains(path))?
+                }
+            }
+        };
+        Some(base)
+    }
+}
+
+/// Drop the items, fields and methods that are less visible than `min`, along with the
+/// impl blocks and relations of the dropped types and fields.
+///
+/// Modules that can't be seen from as far as `min` are dropped with everything inside
+/// them; modules of the crate root are seen by the whole crate. Methods of trait impls
+/// are as visible as the trait and are kept; macros count as `pub` when exported and as
+/// `pub(crate)` otherwise.
+pub fn retain_visible(krate: &mut Crate, min: VisibilityLevel) {
+    if min == VisibilityLevel::Private {
+        return;
+    }
+
+    // Names keep resolving as they did before filtering
+    let index = TypeIndex::new(&krate.root);
+    retain_visible_items(&mut krate.root, min, VisibilityLevel::Public);
+    let paths = krate
+        .root
+        .walk()
+        .flat_map(|m| m.type_names().map(|name| m.item_path(name)))
+        .collect::<HashSet<_>>();
+    retain_impls(&mut krate.root, &paths);
+
+    // Collect the relations again, as fields referring to other types may be gone
+    krate.relations = resolve_relations(&krate.root, &index);
+    krate
+        .relations
+        .retain(|r| paths.contains(&r.from) && paths.contains(&r.to));
+}
+
+/// Only keep the attributes named in `allowed` (e.g. `repr` for `#[repr(C)]`) on structs,
+/// enums, variants and functions; `derive` keeps the derived traits
+pub fn retain_attributes(krate: &mut Crate, allowed: &[String]) {
+    retain_attributes_in(&mut krate.root, allowed);
+}
+
+/// `reach` is how far the items of `module` can be seen from, at most
+fn retain_visible_items(module: &mut Module, min: VisibilityLevel, reach: VisibilityLevel) {
+    let visible = |visibility: &Visibility| visibility.level() >= min;
+
+    module.structs.retain(|s| visible(&s.visibility));
+    for s in &mut module.structs {
+        s.fields.retain(|f| visible(&f.visibility));
+    }
+    module.unions.retain(|u| visible(&u.visibility));
+    for u in &mut module.unions {
+        u.fields.retain(|f| visible(&f.visibility));
+    }
+    module.enums.retain(|e| visible(&e.visibility));
+    module.traits.retain(|t| visible(&t.visibility));
+    module.type_aliases.retain(|t| visible(&t.visibility));
+    module.consts.retain(|c| visible(&c.visibility));
+    module.statics.retain(|s| visible(&s.visibility));
+    module.functions.retain(|f| visible(&f.visibility));
+    module.macros.retain(|m| {
+        let level = if m.exported {
+            VisibilityLevel::Public
+        } else {
+            VisibilityLevel::Crate
+        };
+        level >= min
+    });
+    for block in &mut module.extern_blocks {
+        block.functions.retain(|f| visible(&f.visibility));
+        block.statics.retain(|s| visible(&s.visibility));
+    }
+    module
+        .extern_blocks
+        .retain(|b| !(b.functions.is_empty() && b.statics.is_empty() && b.types.is_empty()));
+    for imp in &mut module.impls {
+        if imp.trait_name.is_none() {
+            imp.functions.retain(|f| visible(&f.visibility));
+        }
+    }
+
+    // Children of the root are seen by the whole crate whatever their visibility,
+    // deeper modules no further than their parent
+    let is_root = module.path == "crate";
+    let module_reach = |submodule: &Module| {
+        let level = submodule.visibility.level();
+        if is_root {
+            level.max(VisibilityLevel::Crate)
+        } else {
+            level.min(reach)
+        }
+    };
+    module.modules.retain(|m| module_reach(m) >= min);
+    for submodule in &mut module.modules {
+        let reach = module_reach(submodule);
+        retain_visible_items(submodule, min, reach);
+    }
+}
+
+/// Drop the impl blocks of types of the crate that are not in `paths` and inherent impl
+/// blocks left empty
+fn retain_impls(module: &mut Module, paths: &HashSet<String>) {
+    module.impls.retain(|imp| {
+        imp.self_path
+            .as_ref()
+            .is_none_or(|path| paths.contains(path))
+            && !(imp.trait_name.is_none() && imp.is_empty())
+    });
+
+    for submodule in &mut module.modules {
+        retain_impls(submodule, paths);
+    }
+}
+
+/// Drop the attributes whose name is not in `allowed`, and the derives unless `derive` is
+fn retain_attributes_in(module: &mut Module, allowed: &[String]) {
+    let allowed_attribute = |attribute: &String| {
+        let name = attribute_name(attribute);
+        allowed.iter().any(|allowed| allowed == name)
+    };
+    let derive = allowed.iter().any(|allowed| allowed == "derive");
+
+    for s in &mut module.structs {
+        s.attributes.retain(allowed_attribute);
+        if !derive {
+            s.derives.clear();
+        }
+    }
+    for e in &mut module.enums {
+        e.attributes.retain(allowed_attribute);
+        for v in &mut e.variants {
+            v.attributes.retain(allowed_attribute);
+        }
+        if !derive {
+            e.derives.clear();
+        }
+    }
+    let functions = module
+        .functions
+        .iter_mut()
+        .chain(module.impls.iter_mut().flat_map(|imp| &mut imp.functions))
+        .chain(
+            module
+                .traits
+                .iter_mut()
+                .flat_map(|t| t.required_methods.iter_mut().chain(&mut t.provided_methods)),
+        )
+        .chain(
+            module
+                .extern_blocks
+                .iter_mut()
+                .flat_map(|b| &mut b.functions),
+        );
+    for f in functions {
+        f.attributes.retain(allowed_attribute);
+    }
+
+    for submodule in &mut module.modules {
+        retain_attributes_in(submodule, allowed);
+    }
+}
+
+/// Resolve the names used across modules and collect the relations of the crate
+fn link_crate(name: String, mut root: Module) -> Crate {
+    root.visibility = Visibility::Public;
+    let index = TypeIndex::new(&root);
+    resolve_imports(&mut root);
+    resolve_impls(&mut root, &index);
+    let traits = root
+        .walk()
+        .flat_map(|m| {
+            m.traits
+                .iter()
+                .map(|t| (m.item_path(&t.name), t.visibility.clone()))
+        })
+        .collect::<HashMap<_, _>>();
+    inherit_trait_visibility(&mut root, &traits);
+    let relations = resolve_relations(&root, &index);
+
+    Crate {
+        name,
+        root,
+        relations,
+    }
+}
+
+/// Make the imports absolute, drop those of other crates and record the names they
+/// bring into scope.
+///
+/// Paths start at `crate`, `self`, `super` or a submodule; `self` imports like
+/// `use std::fmt::{self}` are turned into imports of their module. Names imported from
+/// other crates are kept in `imported_names` so they shadow crate types of the same name.
+fn resolve_imports(module: &mut Module) {
+    let submodules = module
+        .modules
+        .iter()
+        .map(|m| m.name.clone())
+        .collect::<Vec<_>>();
+    let resolve = |import: &str| {
+        let mut segments = import.split("::").peekable();
+        let mut base = match *segments.peek()? {
+            "crate" => {
+                segments.next();
+                vec!["crate"]
+            }
+            "self" => {
+                segments.next();
+                module.path.split("::").collect()
+            }
+            "super" => module.path.split("::").collect(),
+            first if submodules.iter().any(|name| name == first) => {
+                module.path.split("::").collect()
+            }
+            _ => return None,
+        };
+        while segments.next_if_eq(&"super").is_some() {
+            base.pop()?;
+        }
+        base.extend(segments);
+        (!base.is_empty()).then(|| base.join("::"))
+    };
+
+    let mut imports = Vec::new();
+    let mut imported_names = HashMap::new();
+    for import in &module.imports {
+        let (import, alias) = import
+            .split_once(" as ")
+            .map_or((import.as_str(), None), |(import, alias)| {
+                (import, Some(alias))
+            });
+        let import = import.strip_suffix("::self").unwrap_or(import);
+        let path = resolve(import);
+        imports.extend(path.clone());
+
+        let name = alias.unwrap_or_else(|| import.rsplit("::").next().unwrap_or(import));
+        if !matches!(name, "*" | "_") {
+            imported_names.insert(name.to_owned(), path);
+        }
+    }
+    imports.sort();
+    imports.dedup();
+    module.imports = imports;
+    module.imported_names = imported_names;
+
+    for submodule in &mut module.modules {
+        resolve_imports(submodule);
+    }
+}
+
+/// Resolve the implementing type and trait of every impl block
+fn resolve_impls(module: &mut Module, index: &TypeIndex) {
+    let resolved = module
+        .impls
+        .iter()
+        .map(|imp| {
+            let self_path = type_paths(&imp.self_ty)
+                .first()
+                .and_then(|path| index.resolve(module, path));
+            let trait_path = imp
+                .trait_name
+                .as_ref()
+                .and_then(|trait_name| syn::parse_str::<syn::Path>(trait_name).ok())
+                .and_then(|path| index.resolve(module, &path_segments(&path)));
+            (self_path, trait_path)
+        })
+        .collect::<Vec<_>>();
+    for (imp, (self_path, trait_path)) in module.impls.iter_mut().zip(resolved) {
+        imp.self_path = self_path;
+        imp.trait_path = trait_path;
+    }
+
+    for submodule in &mut module.modules {
+        resolve_impls(submodule, index);
+    }
+}
+
+/// Give the methods of trait impls the visibility of their trait, which is `pub` for the
+/// traits of other crates, as they are written without a visibility of their own
+fn inherit_trait_visibility(module: &mut Module, traits: &HashMap<String, Visibility>) {
+    for imp in module
+        .impls
+        .iter_mut()
+        .filter(|imp| imp.trait_name.is_some())
+    {
+        let visibility = imp
+            .trait_path
+            .as_ref()
+            .and_then(|path| traits.get(path))
+            .cloned()
+            .unwrap_or(Visibility::Public);
+        for f in &mut imp.functions {
+            f.visibility.clone_from(&visibility);
+        }
+    }
+
+    for submodule in &mut module.modules {
+        inherit_trait_visibility(submodule, traits);
+    }
+}
+
+/// Find the relations between the items of the crate
+fn resolve_relations(root: &Module, index: &TypeIndex) -> Vec<Relation> {
+    let mut relations = Vec::new();
+    for module in root.walk() {
+        let field_types = module
+            .structs
+            .iter()
+            .map(|s| (&s.name, s.fields.iter().collect::<Vec<_>>()))
+            .chain(
+                module
+                    .enums
+                    .iter()
+                    .map(|e| (&e.name, e.variants.iter().flat_map(|v| &v.fields).collect())),
+            );
+
+        for (name, fields) in field_types {
+            let from = module.item_path(name);
+            for field in fields {
+                for to in type_paths(&field.ty)
+                    .iter()
+                    .filter_map(|used| index.resolve(module, used))
+                {
+                    if to != from {
+                        relations.push(Relation {
+                            kind: RelationKind::Uses,
+                            from: from.clone(),
+                            to,
+                        });
+                    }
+                }
+            }
+        }
+
+        for imp in &module.impls {
+            if let (Some(from), Some(to)) = (&imp.self_path, &imp.trait_path) {
+                relations.push(Relation {
+                    kind: RelationKind::Implements,
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+    }
+
+    relations.sort();
+    relations.dedup();
+    relations
 }
@@ -0,0 +1,687 @@
+//! Format-neutral representation of a parsed crate.
+//!
+//! The `syn` syntax tree is turned into this model and every output format
+//! is rendered from it, so nothing here knows about HTML.
+
+use itertools::Itertools as _;
+use serde::{Serialize, Serializer};
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+/// A parsed crate: its root module and the relations between its items
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Crate {
+    pub name: String,
+    pub root: Module,
+    pub relations: Vec<Relation>,
+}
+
+/// A module with the items declared directly inside it
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct Module {
+    pub name: String,
+    /// Full path of the module, starting with `crate`
+    pub path: String,
+    /// Visibility of the `mod` item; the root module is `pub`
+    pub visibility: Visibility,
+    /// File the module is declared in, `None` when parsed from a source string
+    pub file: Option<PathBuf>,
+    /// Doc comment of the module, from `///` on the `mod` item followed by `//!` inside it
+    pub doc: Option<String>,
+    /// Full paths of the crate items and modules imported with `use`, e.g. `crate::model::Module`
+    /// or `crate::model::*`; imports of other crates are left out
+    pub imports: Vec<String>,
+    /// Names brought into scope by `use`, under their alias if renamed, with the full path
+    /// of the crate item they refer to or `None` for items of other crates
+    #[serde(skip)]
+    pub(crate) imported_names: HashMap<String, Option<String>>,
+    pub structs: Vec<Struct>,
+    pub unions: Vec<Union>,
+    pub enums: Vec<Enum>,
+    pub traits: Vec<Trait>,
+    pub type_aliases: Vec<TypeAlias>,
+    pub consts: Vec<Const>,
+    pub statics: Vec<Static>,
+    pub functions: Vec<Function>,
+    /// `extern` blocks declaring foreign functions, statics and types
+    pub extern_blocks: Vec<ExternBlock>,
+    pub macros: Vec<Macro>,
+    pub impls: Vec<Impl>,
+    pub modules: Vec<Self>,
+}
+
+/// Position of an item in the file of its module
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Location {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column
+    pub column: usize,
+}
+
+/// Visibility of an item, field or method, serialized as written in Rust or `"private"`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Visibility {
+    /// `pub`
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(super)`
+    Super,
+    /// `pub(in path)` with the path as written, e.g. `crate::render`
+    InPath(String),
+    /// No visibility modifier or `pub(self)`
+    #[default]
+    Private,
+}
+
+/// How widely an item is visible, ordered from least to most, for choosing the
+/// minimum visibility of the items to include
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum VisibilityLevel {
+    /// Everything, including private items
+    #[default]
+    Private,
+    /// `pub(super)`, `pub(in path)` and wider
+    Module,
+    /// `pub(crate)` and wider
+    Crate,
+    /// Only `pub`
+    #[cfg_attr(feature = "cli", value(name = "pub"))]
+    Public,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Struct {
+    pub name: String,
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// Traits named in `#[derive(...)]`, as written
+    pub derives: Vec<String>,
+    /// Other attributes as written without `#[...]`, e.g. `repr(C)`
+    pub attributes: Vec<String>,
+    pub fields: Vec<Field>,
+}
+
+/// A struct or enum variant field; tuple fields have no name
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Field {
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Fields of enum variants have the visibility of their enum
+    pub visibility: Visibility,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Union {
+    pub name: String,
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Enum {
+    pub name: String,
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// Traits named in `#[derive(...)]`, as written
+    pub derives: Vec<String>,
+    /// Other attributes as written without `#[...]`, e.g. `non_exhaustive`
+    pub attributes: Vec<String>,
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Variant {
+    pub name: String,
+    pub fields: Vec<Field>,
+    /// The explicit discriminant expression, e.g. `"3"` for `Foo = 3`
+    pub discriminant: Option<String>,
+    pub doc: Option<String>,
+    /// Attributes as written without `#[...]`, e.g. `default`
+    pub attributes: Vec<String>,
+}
+
+/// A trait with its associated items
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Trait {
+    pub name: String,
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// Bounds on `Self`, e.g. `Clone` and `Debug` for `trait Node: Clone + Debug`
+    pub supertraits: Vec<String>,
+    pub types: Vec<AssociatedType>,
+    pub consts: Vec<AssociatedConst>,
+    /// Methods without a default implementation
+    pub required_methods: Vec<Function>,
+    /// Methods with a default implementation
+    pub provided_methods: Vec<Function>,
+}
+
+/// An associated type, e.g. `type Item: Clone = u8;`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct AssociatedType {
+    pub name: String,
+    pub bounds: Vec<String>,
+    /// The default type in a trait or the assigned type in an impl block
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+}
+
+/// An associated constant, e.g. `const MAX: usize = 8;`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct AssociatedConst {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The default value in a trait or the assigned value in an impl block
+    pub value: Option<String>,
+}
+
+/// A type alias, e.g. `type Result<T> = std::result::Result<T, Error>;`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct TypeAlias {
+    pub name: String,
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// The aliased type
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A constant item, e.g. `const MAX_DEPTH: usize = 8;`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Const {
+    pub name: String,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The value expression as written in the source
+    pub value: String,
+}
+
+/// A static item, e.g. `static mut COUNTER: AtomicUsize = AtomicUsize::new(0);`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Static {
+    pub name: String,
+    pub visibility: Visibility,
+    pub location: Location,
+    pub doc: Option<String>,
+    pub is_mut: bool,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The value expression as written in the source
+    pub value: String,
+}
+
+/// A free function or a method inside an impl block or trait
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Function {
+    pub name: String,
+    /// Generic parameters including their bounds, e.g. `"<'a, T: Read + 'a>"`
+    pub generics: Option<String>,
+    pub visibility: Visibility,
+    pub is_async: bool,
+    pub is_const: bool,
+    pub is_unsafe: bool,
+    /// The ABI of an `extern` function, e.g. `"C"`; a bare `extern` is `"C"`
+    pub abi: Option<String>,
+    pub params: Vec<String>,
+    pub return_type: Option<String>,
+    /// Predicates of the `where` clause, e.g. `"T: Send"`
+    pub where_clause: Vec<String>,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// Attributes as written without `#[...]`, e.g. `must_use`
+    pub attributes: Vec<String>,
+}
+
+/// An `extern "ABI" { ... }` block
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ExternBlock {
+    /// The ABI string, e.g. `"C"`; a bare `extern` is `"C"`
+    pub abi: String,
+    pub location: Location,
+    pub functions: Vec<Function>,
+    pub statics: Vec<ForeignStatic>,
+    /// Names of the opaque types declared with `type Name;`
+    pub types: Vec<String>,
+}
+
+/// A static declared in an `extern` block, e.g. `static mut errno: c_int;`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ForeignStatic {
+    pub name: String,
+    pub visibility: Visibility,
+    pub is_mut: bool,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// A declarative macro defined with `macro_rules!`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Macro {
+    pub name: String,
+    /// Whether the macro is exported with `#[macro_export]`
+    pub exported: bool,
+    pub location: Location,
+    pub doc: Option<String>,
+    /// The matcher of every arm as written, e.g. `($name:ident, $($arg:expr),*)`
+    pub arms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct Impl {
+    pub self_ty: String,
+    /// Full path of the implementing type if it is declared in the crate
+    pub self_path: Option<String>,
+    pub trait_name: Option<String>,
+    /// Full path of the implemented trait if it is declared in the crate
+    pub trait_path: Option<String>,
+    pub generics: Option<String>,
+    /// Predicates of the `where` clause, e.g. `"T: Display"`
+    pub where_clause: Vec<String>,
+    /// Associated types with their assigned type, e.g. `type Item = u8;`
+    pub types: Vec<AssociatedType>,
+    /// Associated constants with their assigned value
+    pub consts: Vec<AssociatedConst>,
+    pub functions: Vec<Function>,
+    pub location: Location,
+}
+
+/// A directed relation between two items of the crate, identified by their full paths
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[non_exhaustive]
+pub struct Relation {
+    pub kind: RelationKind,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    /// `from` implements the trait `to`
+    Implements,
+    /// A field of `from` refers to the type `to`
+    Uses,
+}
+
+impl Module {
+    /// Full path of an item declared in this module
+    #[must_use]
+    pub fn item_path(&self, name: &str) -> String {
+        format!("{}::{name}", self.path)
+    }
+
+    /// Names of the types and traits declared in this module
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .chain(self.enums.iter().map(|e| e.name.as_str()))
+            .chain(self.traits.iter().map(|t| t.name.as_str()))
+    }
+
+    /// Whether this module or one of its submodules declares a type or trait
+    #[must_use]
+    pub fn has_types(&self) -> bool {
+        self.walk().any(|m| m.type_names().next().is_some())
+    }
+
+    /// The first paragraph of the doc comment on a single line
+    #[must_use]
+    pub fn summary(&self) -> Option<String> {
+        summary(self.doc.as_deref())
+    }
+
+    /// This module followed by all of its submodules, depth first
+    pub fn walk(&self) -> Box<dyn Iterator<Item = &Self> + '_> {
+        Box::new(std::iter::once(self).chain(self.modules.iter().flat_map(Self::walk)))
+    }
+}
+
+/// A `where` clause with the predicates on a single line, `None` if there are none
+#[must_use]
+pub fn where_clause(predicates: &[String]) -> Option<String> {
+    if predicates.is_empty() {
+        None
+    } else {
+        Some(format!("where {}", predicates.join(", ")))
+    }
+}
+
+/// The first paragraph of a doc comment on a single line
+#[must_use]
+pub fn summary(doc: Option<&str>) -> Option<String> {
+    let summary = doc?
+        .trim()
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .map(str::trim)
+        .join(" ");
+
+    if summary.is_empty() {
+        None
+    } else {
+        Some(summary)
+    }
+}
+
+impl Field {
+    /// The field name, or its index for tuple fields
+    #[must_use]
+    pub fn display_name(&self, index: usize) -> String {
+        self.name.clone().unwrap_or_else(|| index.to_string())
+    }
+}
+
+impl Variant {
+    /// The variant's fields formatted as `{ a: A }` or `(A, B)`, `None` for unit variants
+    #[must_use]
+    pub fn data(&self) -> Option<String> {
+        if self.fields.is_empty() {
+            return None;
+        }
+
+        let parts = self
+            .fields
+            .iter()
+            .map(|f| {
+                f.name
+                    .as_ref()
+                    .map_or_else(|| f.ty.clone(), |name| format!("{name}: {}", f.ty))
+            })
+            .join(", ");
+
+        if self.fields.iter().any(|f| f.name.is_some()) {
+            Some(format!("{{ {parts} }}"))
+        } else {
+            Some(format!("({parts})"))
+        }
+    }
+}
+
+/// Formats the variant as declared, e.g. `Some(T)` or `Move { x: i32 }`
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.data() {
+            Some(data) if data.starts_with('(') => write!(f, "{}{data}", self.name)?,
+            Some(data) => write!(f, "{} {data}", self.name)?,
+            None => write!(f, "{}", self.name)?,
+        }
+        if let Some(discriminant) = &self.discriminant {
+            write!(f, " = {discriminant}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Trait {
+    /// The supertraits formatted as `: Clone + Debug`, `None` if there are none
+    #[must_use]
+    pub fn supertraits(&self) -> Option<String> {
+        if self.supertraits.is_empty() {
+            None
+        } else {
+            Some(format!(": {}", self.supertraits.join(" + ")))
+        }
+    }
+}
+
+/// Formats the associated type as declared, e.g. `type Item: Clone = u8`
+impl fmt::Display for AssociatedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type {}", self.name)?;
+        if !self.bounds.is_empty() {
+            write!(f, ": {}", self.bounds.join(" + "))?;
+        }
+        if let Some(ty) = &self.ty {
+            write!(f, " = {ty}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the associated constant as declared, e.g. `const MAX: usize = 8`
+impl fmt::Display for AssociatedConst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const {}: {}", self.name, self.ty)?;
+        if let Some(value) = &self.value {
+            write!(f, " = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the alias as declared, e.g. `type Result<T> = std::result::Result<T, Error>`
+impl fmt::Display for TypeAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "type {}{} = {}",
+            self.name,
+            self.generics.as_deref().unwrap_or_default(),
+            self.ty
+        )
+    }
+}
+
+/// Formats the constant with its value shortened, e.g. `const MAX_DEPTH: usize = 8`
+impl fmt::Display for Const {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "const {}: {} = {}",
+            self.name,
+            self.ty,
+            abbreviate(&self.value)
+        )
+    }
+}
+
+/// Formats the static with its value shortened, e.g. `static mut COUNTER: u32 = 0`
+impl fmt::Display for Static {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "static {}{}: {} = {}",
+            if self.is_mut { "mut " } else { "" },
+            self.name,
+            self.ty,
+            abbreviate(&self.value)
+        )
+    }
+}
+
+/// Longest value expression shown before it is cut off with `…`
+pub const VALUE_LENGTH: usize = 40;
+
+/// A value expression on a single line, cut off after [`VALUE_LENGTH`] characters
+#[must_use]
+pub fn abbreviate(value: &str) -> String {
+    let value = value.split_whitespace().join(" ");
+    if value.chars().count() <= VALUE_LENGTH {
+        value
+    } else {
+        let mut short = value.chars().take(VALUE_LENGTH - 1).collect::<String>();
+        short.push('…');
+        short
+    }
+}
+
+impl Function {
+    /// The `const async unsafe extern "ABI"` keywords of the signature, if any
+    #[must_use]
+    pub fn modifiers(&self) -> Option<String> {
+        let modifiers = [
+            (self.is_const, "const".to_owned()),
+            (self.is_async, "async".to_owned()),
+            (self.is_unsafe, "unsafe".to_owned()),
+        ]
+        .into_iter()
+        .filter_map(|(set, keyword)| set.then_some(keyword))
+        .chain(self.abi.as_ref().map(|abi| format!("extern \"{abi}\"")))
+        .join(" ");
+
+        if modifiers.is_empty() {
+            None
+        } else {
+            Some(modifiers)
+        }
+    }
+
+    /// Whether the function takes `self` in any form, i.e. is not an associated function
+    #[must_use]
+    pub fn takes_self(&self) -> bool {
+        self.params.first().is_some_and(|param| {
+            param
+                .trim_start_matches('&')
+                .split_whitespace()
+                .find(|word| !word.starts_with('\'') && *word != "mut")
+                .is_some_and(|word| word == "self" || word.starts_with("self:"))
+        })
+    }
+
+    /// The signature as declared, e.g. `pub async fn load(path: &Path) -> Result<Data>`
+    #[must_use]
+    pub fn signature(&self) -> String {
+        let keywords = self
+            .visibility
+            .keyword()
+            .into_iter()
+            .chain(self.modifiers())
+            .chain(Some("fn".to_owned()))
+            .join(" ");
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map(|ty| format!(" -> {ty}"))
+            .unwrap_or_default();
+
+        format!(
+            "{keywords} {}{}({}){return_type}{}",
+            self.name,
+            self.generics.as_deref().unwrap_or_default(),
+            self.params.join(", "),
+            where_clause(&self.where_clause)
+                .map(|clause| format!(" {clause}"))
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Formats the static as declared, e.g. `static mut errno: c_int`
+impl fmt::Display for ForeignStatic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "static {}{}: {}",
+            if self.is_mut { "mut " } else { "" },
+            self.name,
+            self.ty
+        )
+    }
+}
+
+impl Impl {
+    /// The header of the impl block, e.g. `impl<T> Display for Wrapper<T>`
+    #[must_use]
+    pub fn header(&self) -> String {
+        let generics = self.generics.as_deref().unwrap_or_default();
+        let header = self.trait_name.as_ref().map_or_else(
+            || format!("impl{generics} {}", self.self_ty),
+            |trait_name| format!("impl{generics} {trait_name} for {}", self.self_ty),
+        );
+        match where_clause(&self.where_clause) {
+            Some(clause) => format!("{header} {clause}"),
+            None => header,
+        }
+    }
+
+    /// The associated types and constants as declared, e.g. `type Item = u8`
+    pub fn associated_items(&self) -> impl Iterator<Item = String> {
+        self.types
+            .iter()
+            .map(ToString::to_string)
+            .chain(self.consts.iter().map(ToString::to_string))
+    }
+
+    /// Whether the impl block has no items to show
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.consts.is_empty() && self.functions.is_empty()
+    }
+}
+
+impl Visibility {
+    /// The visibility as written in Rust, `None` for private items
+    #[must_use]
+    pub fn keyword(&self) -> Option<String> {
+        (*self != Self::Private).then(|| self.to_string())
+    }
+
+    /// How widely the item is visible
+    #[must_use]
+    pub const fn level(&self) -> VisibilityLevel {
+        match self {
+            Self::Public => VisibilityLevel::Public,
+            Self::Crate => VisibilityLevel::Crate,
+            Self::Super | Self::InPath(_) => VisibilityLevel::Module,
+            Self::Private => VisibilityLevel::Private,
+        }
+    }
+}
+
+/// Formats the visibility as written in Rust, or `private`
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Public => write!(f, "pub"),
+            Self::Crate => write!(f, "pub(crate)"),
+            Self::Super => write!(f, "pub(super)"),
+            Self::InPath(path) => write!(f, "pub(in {path})"),
+            Self::Private => write!(f, "private"),
+        }
+    }
+}
+
+impl Serialize for Visibility {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,226 @@
+//! D2 diagram output.
+
+use std::{collections::HashMap, fmt::Write as _};
+
+use super::{methods_by_type, uml_visibility};
+use crate::model::{Crate, Function, Module, RelationKind, Trait, summary};
+
+/// Render the crate as a D2 diagram with one container per module
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let methods = methods_by_type(krate);
+    let mut out = String::from("direction: down\n\n");
+
+    write_container(&mut out, &krate.root, &krate.name, &methods, 0)?;
+
+    if !krate.relations.is_empty() {
+        out.push('\n');
+    }
+    for relation in &krate.relations {
+        let style = match relation.kind {
+            RelationKind::Implements => {
+                " {\n  style.stroke-dash: 3\n  target-arrowhead.shape: triangle\n  target-arrowhead.style.filled: false\n}"
+            }
+            RelationKind::Uses => "",
+        };
+        writeln!(
+            out,
+            "{} -> {}{style}",
+            key_path(&relation.from),
+            key_path(&relation.to)
+        )?;
+    }
+
+    Ok(out)
+}
+
+fn write_container(
+    out: &mut String,
+    module: &Module,
+    crate_name: &str,
+    methods: &HashMap<&str, Vec<&Function>>,
+    depth: usize,
+) -> anyhow::Result<()> {
+    if !module.has_types() {
+        return Ok(());
+    }
+
+    let indent = "  ".repeat(depth);
+    writeln!(out, "{indent}{}: {{", quote(&module.name))?;
+    let label = if depth == 0 { crate_name } else { &module.name };
+    writeln!(out, "{indent}  label: {}", quote(label))?;
+    write_tooltip(out, &format!("{indent}  "), module.doc.as_deref())?;
+
+    for s in &module.structs {
+        writeln!(out, "{indent}  {}: {{", quote(&s.name))?;
+        writeln!(out, "{indent}    shape: class")?;
+        if let Some(generics) = &s.generics {
+            writeln!(
+                out,
+                "{indent}    label: {}",
+                quote(&format!("{}{generics}", s.name))
+            )?;
+        }
+        write_tooltip(out, &format!("{indent}    "), s.doc.as_deref())?;
+        for (i, field) in s.fields.iter().enumerate() {
+            writeln!(
+                out,
+                "{indent}    {}: {}",
+                quote(&format!(
+                    "{}{}",
+                    uml_visibility(&field.visibility),
+                    field.display_name(i)
+                )),
+                quote(&field.ty)
+            )?;
+        }
+        write_methods(
+            out,
+            &indent,
+            methods.get(module.item_path(&s.name).as_str()),
+        )?;
+        writeln!(out, "{indent}  }}")?;
+    }
+
+    for e in &module.enums {
+        writeln!(out, "{indent}  {}: {{", quote(&e.name))?;
+        writeln!(out, "{indent}    shape: class")?;
+        writeln!(
+            out,
+            "{indent}    label: {}",
+            quote(&format!(
+                "<<enum>> {}{}",
+                e.name,
+                e.generics.as_deref().unwrap_or_default()
+            ))
+        )?;
+        write_tooltip(out, &format!("{indent}    "), e.doc.as_deref())?;
+        for variant in &e.variants {
+            let value = variant
+                .data()
+                .into_iter()
+                .chain(variant.discriminant.as_ref().map(|d| format!("= {d}")))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if value.is_empty() {
+                writeln!(out, "{indent}    {}", quote(&variant.name))?;
+            } else {
+                writeln!(
+                    out,
+                    "{indent}    {}: {}",
+                    quote(&variant.name),
+                    quote(&value)
+                )?;
+            }
+        }
+        write_methods(
+            out,
+            &indent,
+            methods.get(module.item_path(&e.name).as_str()),
+        )?;
+        writeln!(out, "{indent}  }}")?;
+    }
+
+    for t in &module.traits {
+        write_trait(
+            out,
+            &indent,
+            t,
+            methods.get(module.item_path(&t.name).as_str()),
+        )?;
+    }
+
+    for submodule in &module.modules {
+        write_container(out, submodule, crate_name, methods, depth + 1)?;
+    }
+
+    writeln!(out, "{indent}}}")?;
+    Ok(())
+}
+
+/// A trait as a class shape with its associated items, own methods and impl methods
+fn write_trait(
+    out: &mut String,
+    indent: &str,
+    t: &Trait,
+    methods: Option<&Vec<&Function>>,
+) -> anyhow::Result<()> {
+    writeln!(out, "{indent}  {}: {{", quote(&t.name))?;
+    writeln!(out, "{indent}    shape: class")?;
+    writeln!(
+        out,
+        "{indent}    label: {}",
+        quote(&format!(
+            "<<trait>> {}{}",
+            t.name,
+            t.generics.as_deref().unwrap_or_default()
+        ))
+    )?;
+    write_tooltip(out, &format!("{indent}    "), t.doc.as_deref())?;
+    for ty in &t.types {
+        match &ty.ty {
+            Some(default) => writeln!(
+                out,
+                "{indent}    {}: {}",
+                quote(&format!("type {}", ty.name)),
+                quote(default)
+            )?,
+            None => writeln!(out, "{indent}    {}", quote(&format!("type {}", ty.name)))?,
+        }
+    }
+    for c in &t.consts {
+        writeln!(
+            out,
+            "{indent}    {}: {}",
+            quote(&format!("const {}", c.name)),
+            quote(&c.ty)
+        )?;
+    }
+    let trait_methods = t
+        .required_methods
+        .iter()
+        .chain(&t.provided_methods)
+        .chain(methods.into_iter().flatten().copied())
+        .collect::<Vec<_>>();
+    write_methods(out, indent, Some(&trait_methods))?;
+    writeln!(out, "{indent}  }}")?;
+    Ok(())
+}
+
+/// Methods are the members of a class shape whose key contains parentheses
+fn write_methods(
+    out: &mut String,
+    indent: &str,
+    methods: Option<&Vec<&Function>>,
+) -> anyhow::Result<()> {
+    for f in methods.into_iter().flatten() {
+        let key = quote(&format!(
+            "{}{}({})",
+            uml_visibility(&f.visibility),
+            f.name,
+            f.params.join(", ")
+        ));
+        match &f.return_type {
+            Some(ty) => writeln!(out, "{indent}    {key}: {}", quote(ty))?,
+            None => writeln!(out, "{indent}    {key}")?,
+        }
+    }
+    Ok(())
+}
+
+/// The first paragraph of a doc comment as tooltip, unquoted so it isn't taken for a class field
+fn write_tooltip(out: &mut String, indent: &str, doc: Option<&str>) -> anyhow::Result<()> {
+    if let Some(summary) = summary(doc) {
+        writeln!(out, "{indent}tooltip: {}", quote(&summary))?;
+    }
+    Ok(())
+}
+
+/// The D2 key of an item or module, e.g. `"crate"."shapes"."Polygon"` for `crate::shapes::Polygon`
+fn key_path(path: &str) -> String {
+    path.split("::").map(quote).collect::<Vec<_>>().join(".")
+}
+
+/// Quote a key or value, which also keeps names like `Style` from being read as keywords
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,230 @@
+//! Graphviz DOT output.
+
+use std::{collections::HashMap, fmt::Write as _};
+
+use super::{methods_by_type, uml_visibility};
+use crate::model::{Crate, Function, Module, RelationKind, Trait, summary};
+
+/// Render the crate as a Graphviz digraph with one cluster per module
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let methods = methods_by_type(krate);
+    let mut out = format!(
+        "digraph \"{}\" {{\n    compound=true;\n    rankdir=TB;\n    node [shape=record, fontname=\"monospace\", fontsize=10];\n    edge [fontname=\"monospace\", fontsize=9];\n\n",
+        escape_string(&krate.name)
+    );
+
+    write_cluster(&mut out, &krate.root, &methods, 1)?;
+    out.push('\n');
+
+    for relation in &krate.relations {
+        let style = match relation.kind {
+            RelationKind::Implements => "style=dashed, arrowhead=empty",
+            RelationKind::Uses => "arrowhead=vee",
+        };
+        writeln!(
+            out,
+            "    \"{}\" -> \"{}\" [{style}];",
+            relation.from, relation.to
+        )?;
+    }
+
+    out.push_str("}\n");
+    Ok(out)
+}
+
+fn write_cluster(
+    out: &mut String,
+    module: &Module,
+    methods: &HashMap<&str, Vec<&Function>>,
+    depth: usize,
+) -> anyhow::Result<()> {
+    if !module.has_types() {
+        return Ok(());
+    }
+
+    let indent = "    ".repeat(depth);
+    writeln!(
+        out,
+        "{indent}subgraph \"cluster_{}\" {{",
+        module.path.replace("::", "__")
+    )?;
+    writeln!(
+        out,
+        "{indent}    label=\"{}\";",
+        escape_string(&module.name)
+    )?;
+
+    for s in &module.structs {
+        let path = module.item_path(&s.name);
+        let fields = s
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                format!(
+                    "{}{}: {}",
+                    uml_visibility(&f.visibility),
+                    f.display_name(i),
+                    f.ty
+                )
+            })
+            .collect::<Vec<_>>();
+        let title = format!("{}{}", s.name, s.generics.as_deref().unwrap_or_default());
+
+        write_node(
+            out,
+            &indent,
+            &path,
+            &escape_record(&title),
+            &fields,
+            methods.get(path.as_str()),
+            s.doc.as_deref(),
+        )?;
+    }
+
+    for e in &module.enums {
+        let path = module.item_path(&e.name);
+        let variants = e
+            .variants
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let title = format!(
+            "\\<\\<enum\\>\\>\\n{}",
+            escape_record(&format!(
+                "{}{}",
+                e.name,
+                e.generics.as_deref().unwrap_or_default()
+            ))
+        );
+
+        write_node(
+            out,
+            &indent,
+            &path,
+            &title,
+            &variants,
+            methods.get(path.as_str()),
+            e.doc.as_deref(),
+        )?;
+    }
+
+    for t in &module.traits {
+        let path = module.item_path(&t.name);
+        write_trait(out, &indent, &path, t, methods.get(path.as_str()))?;
+    }
+
+    for submodule in &module.modules {
+        write_cluster(out, submodule, methods, depth + 1)?;
+    }
+
+    writeln!(out, "{indent}}}")?;
+    Ok(())
+}
+
+/// A trait as a record node with its associated items, own methods and impl methods
+fn write_trait(
+    out: &mut String,
+    indent: &str,
+    path: &str,
+    t: &Trait,
+    methods: Option<&Vec<&Function>>,
+) -> anyhow::Result<()> {
+    let members = t
+        .types
+        .iter()
+        .map(ToString::to_string)
+        .chain(t.consts.iter().map(ToString::to_string))
+        .collect::<Vec<_>>();
+    let trait_methods = t
+        .required_methods
+        .iter()
+        .chain(&t.provided_methods)
+        .chain(methods.into_iter().flatten().copied())
+        .collect::<Vec<_>>();
+    let title = format!(
+        "\\<\\<trait\\>\\>\\n{}",
+        escape_record(&format!(
+            "{}{}",
+            t.name,
+            t.generics.as_deref().unwrap_or_default()
+        ))
+    );
+
+    write_node(
+        out,
+        indent,
+        path,
+        &title,
+        &members,
+        Some(&trait_methods),
+        t.doc.as_deref(),
+    )
+}
+
+/// Write a record node with the (already escaped) title, members and methods compartments
+/// and the first paragraph of its doc comment as tooltip
+fn write_node(
+    out: &mut String,
+    indent: &str,
+    id: &str,
+    title: &str,
+    members: &[String],
+    methods: Option<&Vec<&Function>>,
+    doc: Option<&str>,
+) -> anyhow::Result<()> {
+    let members = lines(members.iter().map(String::as_str));
+    let methods = methods
+        .into_iter()
+        .flatten()
+        .map(|f| {
+            format!(
+                "{}{}({}){}",
+                uml_visibility(&f.visibility),
+                f.name,
+                f.params.join(", "),
+                f.return_type
+                    .as_ref()
+                    .map(|ty| format!(" -> {ty}"))
+                    .unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    let methods = lines(methods.iter().map(String::as_str));
+
+    let tooltip = summary(doc)
+        .map(|summary| format!(", tooltip=\"{}\"", escape_string(&summary)))
+        .unwrap_or_default();
+
+    writeln!(
+        out,
+        "{indent}    \"{id}\" [label=\"{{{title}|{members}|{methods}}}\"{tooltip}];"
+    )?;
+    Ok(())
+}
+
+/// Left-aligned record lines
+fn lines<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    lines.fold(String::new(), |mut out, line| {
+        out.push_str(&escape_record(line));
+        out.push_str("\\l");
+        out
+    })
+}
+
+/// Escape text for a record label, where braces, pipes and angle brackets are syntax
+fn escape_record(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '{' | '}' | '|' | '<' | '>' | '"' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape text for a quoted DOT string
+fn escape_string(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
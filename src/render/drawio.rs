@@ -0,0 +1,210 @@
+//! Uncompressed draw.io (diagrams.net) output drawn with the built-in [`layout`](super::layout).
+
+use std::{collections::HashMap, fmt::Write as _};
+
+use super::layout::{self, Card, CardKind, Frame, LINE_HEIGHT, PADDING, Rect};
+use crate::model::{Crate, RelationKind};
+
+const FONT: &str = "fontFamily=Courier New;fontSize=12;";
+
+/// Render the crate as a draw.io file with one page per top-level module
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let mut out = String::from("<mxfile host=\"diagen\">\n");
+
+    let pages = pages(krate);
+    let laid_out = pages
+        .iter()
+        .map(|page| (page, layout::layout(page)))
+        .filter(|(_, layout)| !layout.frames.is_empty());
+    for (i, (page, layout)) in laid_out.enumerate() {
+        write_page(&mut out, i, page, &layout)?;
+    }
+
+    out.push_str("</mxfile>\n");
+    Ok(out)
+}
+
+/// The items of the crate root and every top-level module as crates of their own
+fn pages(krate: &Crate) -> Vec<Crate> {
+    let mut root = krate.root.clone();
+    root.modules.clear();
+
+    std::iter::once(Crate {
+        name: krate.name.clone(),
+        root,
+        relations: krate.relations.clone(),
+    })
+    .chain(krate.root.modules.iter().map(|module| Crate {
+        name: module.name.clone(),
+        root: module.clone(),
+        relations: krate.relations.clone(),
+    }))
+    .collect()
+}
+
+fn write_page(
+    out: &mut String,
+    index: usize,
+    page: &Crate,
+    layout: &layout::Layout,
+) -> anyhow::Result<()> {
+    writeln!(
+        out,
+        r#"  <diagram id="page-{index}" name="{}">"#,
+        escape(&page.name)
+    )?;
+    writeln!(
+        out,
+        r#"    <mxGraphModel grid="1" gridSize="10" guides="1" tooltips="1" connect="1" arrows="1" fold="1" page="0" pageScale="1" pageWidth="{}" pageHeight="{}" math="0" shadow="0">"#,
+        layout.width.ceil(),
+        layout.height.ceil()
+    )?;
+    out.push_str(
+        "      <root>\n        <mxCell id=\"0\"/>\n        <mxCell id=\"1\" parent=\"0\"/>\n",
+    );
+
+    // Frames come before the frames nested in them, so parents are always written first
+    for (i, frame) in layout.frames.iter().enumerate() {
+        let parent = innermost_frame(&layout.frames[..i], &frame.rect);
+        write_frame(out, i, frame, parent.map(|p| (p, layout.frames[p].rect)))?;
+    }
+
+    let mut ids = HashMap::new();
+    for (i, card) in layout.cards.iter().enumerate() {
+        let parent = innermost_frame(&layout.frames, &card.rect);
+        write_card(out, i, card, parent.map(|p| (p, layout.frames[p].rect)))?;
+        if let Some(path) = &card.path {
+            ids.insert(path.as_str(), i);
+        }
+    }
+
+    let edges = page.relations.iter().filter_map(|relation| {
+        Some((
+            relation.kind,
+            *ids.get(relation.from.as_str())?,
+            *ids.get(relation.to.as_str())?,
+        ))
+    });
+    for (i, (kind, from, to)) in edges.enumerate() {
+        let style = match kind {
+            RelationKind::Implements => "dashed=1;endArrow=block;endFill=0;",
+            RelationKind::Uses => "endArrow=open;endFill=0;",
+        };
+        writeln!(
+            out,
+            r#"        <mxCell id="edge-{i}" style="edgeStyle=orthogonalEdgeStyle;rounded=0;html=0;{style}" edge="1" parent="1" source="card-{from}" target="card-{to}"><mxGeometry relative="1" as="geometry"/></mxCell>"#
+        )?;
+    }
+
+    out.push_str("      </root>\n    </mxGraphModel>\n  </diagram>\n");
+    Ok(())
+}
+
+/// The index of the smallest of `frames` containing `rect`
+fn innermost_frame(frames: &[Frame], rect: &Rect) -> Option<usize> {
+    frames
+        .iter()
+        .enumerate()
+        .filter(|(_, frame)| {
+            frame.rect.x <= rect.x
+                && frame.rect.y <= rect.y
+                && rect.right() <= frame.rect.right()
+                && rect.bottom() <= frame.rect.bottom()
+        })
+        .min_by(|(_, a), (_, b)| {
+            (a.rect.width * a.rect.height).total_cmp(&(b.rect.width * b.rect.height))
+        })
+        .map(|(i, _)| i)
+}
+
+/// A module as a container whose children move with it
+fn write_frame(
+    out: &mut String,
+    index: usize,
+    frame: &Frame,
+    parent: Option<(usize, Rect)>,
+) -> anyhow::Result<()> {
+    let (parent, origin) = parent_of(parent);
+    writeln!(
+        out,
+        r#"        <mxCell id="frame-{index}" value="{name}" style="swimlane;container=1;collapsible=0;html=0;rounded=1;arcSize=4;startSize={header};fontStyle=1;fontFamily=Courier New;fontSize=14;fillColor=#e9ecef;swimlaneFillColor=#fafafa;strokeColor=#d0d0d0;" vertex="1" parent="{parent}">{geometry}</mxCell>"#,
+        name = escape(&frame.name),
+        header = layout::FRAME_HEADER,
+        geometry = geometry(&frame.rect, origin),
+    )?;
+    Ok(())
+}
+
+/// A UML class shape: the title in the header and one text cell per compartment
+fn write_card(
+    out: &mut String,
+    index: usize,
+    card: &Card,
+    parent: Option<(usize, Rect)>,
+) -> anyhow::Result<()> {
+    let (fill, header_fill) = match card.kind {
+        CardKind::Struct => ("#ffffff", "#f8f9fa"),
+        CardKind::Enum => ("#f0f9f0", "#e8f5e8"),
+        CardKind::Trait => ("#f5f0ff", "#ece4fb"),
+        CardKind::Function => ("#f0f4ff", "#f0f4ff"),
+        CardKind::Impl => ("#fff4e6", "#f4ede6"),
+    };
+    let header = 2.0f64.mul_add(PADDING, LINE_HEIGHT);
+
+    let (parent, origin) = parent_of(parent);
+    writeln!(
+        out,
+        r#"        <mxCell id="card-{index}" value="{title}" style="swimlane;collapsible=0;html=0;rounded=0;startSize={header};fontStyle=1;{FONT}fillColor={header_fill};swimlaneFillColor={fill};strokeColor=#e0e0e0;" vertex="1" parent="{parent}">{geometry}</mxCell>"#,
+        title = escape(&card.title),
+        geometry = geometry(&card.rect, origin),
+    )?;
+
+    let mut top = header;
+    for (i, lines) in card.compartments.iter().enumerate() {
+        #[expect(clippy::cast_precision_loss, reason = "line counts are small")]
+        let height = (lines.len() as f64).mul_add(LINE_HEIGHT, 2.0 * PADDING);
+        writeln!(
+            out,
+            r#"        <mxCell id="card-{index}-{i}" value="{value}" style="text;html=0;align=left;verticalAlign=top;spacingLeft={PADDING};spacingTop=4;{FONT}strokeColor=#e0e0e0;fillColor=none;" vertex="1" parent="card-{index}">{geometry}</mxCell>"#,
+            value = escape(&lines.join("\n")),
+            geometry = geometry(
+                &Rect {
+                    x: 0.0,
+                    y: top,
+                    width: card.rect.width,
+                    height,
+                },
+                (0.0, 0.0)
+            ),
+        )?;
+        top += height;
+    }
+    Ok(())
+}
+
+/// The id of the parent cell and the origin its children are positioned relative to
+fn parent_of(frame: Option<(usize, Rect)>) -> (String, (f64, f64)) {
+    frame.map_or_else(
+        || ("1".to_owned(), (0.0, 0.0)),
+        |(i, rect)| (format!("frame-{i}"), (rect.x, rect.y)),
+    )
+}
+
+fn geometry(rect: &Rect, (x, y): (f64, f64)) -> String {
+    format!(
+        r#"<mxGeometry x="{}" y="{}" width="{}" height="{}" as="geometry"/>"#,
+        rect.x - x,
+        rect.y - y,
+        rect.width,
+        rect.height
+    )
+}
+
+/// Escape text for an XML attribute, keeping line breaks
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#xa;")
+}
@@ -0,0 +1,279 @@
+//! Excalidraw scene output drawn with the built-in [`layout`](super::layout).
+
+use serde_json::{Map, Value, json};
+
+use super::layout::{self, Card, CardKind, Edge, FRAME_HEADER, Frame, LINE_HEIGHT, PADDING, Rect};
+use crate::model::{Crate, RelationKind};
+
+const FONT_SIZE: f64 = 12.0;
+/// Cascadia, the monospace font of Excalidraw
+const FONT_FAMILY: u8 = 3;
+
+/// Render the crate as an Excalidraw scene of module frames, item cards and arrows
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let layout = layout::layout(krate);
+    let mut elements = Vec::new();
+
+    for (i, frame) in layout.frames.iter().enumerate() {
+        let groups = groups(&format!("frame-{i}"), &frame.rect, &layout.frames);
+        frame_elements(&mut elements, i, frame, &groups);
+    }
+    for (i, card) in layout.cards.iter().enumerate() {
+        let arrows = layout
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edge)| edge.from == i || edge.to == i)
+            .map(|(j, _)| json!({ "id": format!("edge-{j}"), "type": "arrow" }))
+            .collect();
+        let groups = groups(&format!("card-{i}"), &card.rect, &layout.frames);
+        card_elements(&mut elements, i, card, arrows, &groups);
+    }
+    for (i, edge) in layout.edges.iter().enumerate() {
+        elements.push(arrow(i, edge));
+    }
+
+    let scene = json!({
+        "type": "excalidraw",
+        "version": 2,
+        "source": format!("diagen {}", env!("CARGO_PKG_VERSION")),
+        "elements": elements,
+        "appState": { "gridSize": null, "viewBackgroundColor": "#ffffff" },
+        "files": {},
+    });
+    Ok(serde_json::to_string_pretty(&scene)?)
+}
+
+/// The group ids of a frame or card: its own followed by those of the frames around it,
+/// innermost first, so moving a module moves everything inside it
+fn groups(id: &str, rect: &Rect, frames: &[Frame]) -> Value {
+    let mut around = frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| (format!("frame-{i}"), frame.rect))
+        .filter(|(frame_id, frame)| frame_id != id && frame.contains(rect))
+        .collect::<Vec<_>>();
+    around.sort_by(|(_, a), (_, b)| (a.width * a.height).total_cmp(&(b.width * b.height)));
+
+    std::iter::once(id.to_owned())
+        .chain(around.into_iter().map(|(frame_id, _)| frame_id))
+        .collect()
+}
+
+/// A module: a rounded box with its name, grouped with its contents so they move together
+fn frame_elements(elements: &mut Vec<Value>, index: usize, frame: &Frame, group: &Value) {
+    let id = format!("frame-{index}");
+
+    let mut rect = shape("rectangle", &id, &frame.rect, elements.len());
+    rect.extend([
+        ("backgroundColor".to_owned(), json!("#fafafa")),
+        ("strokeColor".to_owned(), json!("#adb5bd")),
+        ("roundness".to_owned(), json!({ "type": 3 })),
+        ("groupIds".to_owned(), group.clone()),
+    ]);
+    elements.push(Value::Object(rect));
+
+    let mut name = text(
+        &format!("{id}-name"),
+        &frame.name,
+        frame.rect.x + PADDING,
+        frame.rect.y + (FRAME_HEADER - LINE_HEIGHT) / 2.0,
+        elements.len(),
+    );
+    name.insert("groupIds".to_owned(), group.clone());
+    elements.push(Value::Object(name));
+}
+
+/// An item: a box with the title and a text per compartment separated by lines
+fn card_elements(
+    elements: &mut Vec<Value>,
+    index: usize,
+    card: &Card,
+    arrows: Vec<Value>,
+    group: &Value,
+) {
+    let id = format!("card-{index}");
+    let background = match card.kind {
+        CardKind::Struct => "#ffffff",
+        CardKind::Enum => "#f0f9f0",
+        CardKind::Trait => "#f5f0ff",
+        CardKind::Function => "#f0f4ff",
+        CardKind::Impl => "#fff4e6",
+    };
+
+    let mut rect = shape("rectangle", &id, &card.rect, elements.len());
+    rect.extend([
+        ("backgroundColor".to_owned(), json!(background)),
+        ("boundElements".to_owned(), Value::Array(arrows)),
+        ("groupIds".to_owned(), group.clone()),
+    ]);
+    elements.push(Value::Object(rect));
+
+    let mut title = text(
+        &format!("{id}-title"),
+        &card.title,
+        card.rect.x + PADDING,
+        card.rect.y + PADDING,
+        elements.len(),
+    );
+    title.insert("groupIds".to_owned(), group.clone());
+    elements.push(Value::Object(title));
+
+    let mut top = card.rect.y + 2.0f64.mul_add(PADDING, LINE_HEIGHT);
+    for (i, lines) in card.compartments.iter().enumerate() {
+        let mut separator = shape(
+            "line",
+            &format!("{id}-line-{i}"),
+            &Rect {
+                x: card.rect.x,
+                y: top,
+                width: card.rect.width,
+                height: 0.0,
+            },
+            elements.len(),
+        );
+        separator.extend([
+            (
+                "points".to_owned(),
+                json!([[0.0, 0.0], [card.rect.width, 0.0]]),
+            ),
+            ("groupIds".to_owned(), group.clone()),
+        ]);
+        elements.push(Value::Object(separator));
+
+        let mut text = text(
+            &format!("{id}-text-{i}"),
+            &lines.join("\n"),
+            card.rect.x + PADDING,
+            top + PADDING,
+            elements.len(),
+        );
+        text.insert("groupIds".to_owned(), group.clone());
+        elements.push(Value::Object(text));
+
+        #[expect(clippy::cast_precision_loss, reason = "line counts are small")]
+        {
+            top += (lines.len() as f64).mul_add(LINE_HEIGHT, 2.0 * PADDING);
+        }
+    }
+}
+
+/// A relation as an arrow bound to its cards, so it follows them when they are moved
+fn arrow(index: usize, edge: &Edge) -> Value {
+    let (x, y) = edge.points.first().copied().unwrap_or_default();
+    let (min_x, max_x) = edge
+        .points
+        .iter()
+        .fold((x, x), |(min, max), &(px, _)| (min.min(px), max.max(px)));
+    let (min_y, max_y) = edge
+        .points
+        .iter()
+        .fold((y, y), |(min, max), &(_, py)| (min.min(py), max.max(py)));
+
+    let mut arrow = shape(
+        "arrow",
+        &format!("edge-{index}"),
+        &Rect {
+            x,
+            y,
+            width: max_x - min_x,
+            height: max_y - min_y,
+        },
+        index,
+    );
+    let (style, head) = match edge.kind {
+        RelationKind::Implements => ("dashed", "triangle"),
+        RelationKind::Uses => ("solid", "arrow"),
+    };
+    arrow.extend([
+        ("strokeColor".to_owned(), json!("#555555")),
+        ("strokeStyle".to_owned(), json!(style)),
+        (
+            "points".to_owned(),
+            edge.points
+                .iter()
+                .map(|(px, py)| json!([px - x, py - y]))
+                .collect(),
+        ),
+        (
+            "startBinding".to_owned(),
+            json!({ "elementId": format!("card-{}", edge.from), "focus": 0, "gap": 1 }),
+        ),
+        (
+            "endBinding".to_owned(),
+            json!({ "elementId": format!("card-{}", edge.to), "focus": 0, "gap": 1 }),
+        ),
+        ("startArrowhead".to_owned(), Value::Null),
+        ("endArrowhead".to_owned(), json!(head)),
+        ("lastCommittedPoint".to_owned(), Value::Null),
+    ]);
+    Value::Object(arrow)
+}
+
+/// Left-aligned monospace text with its top left corner at `x`, `y`
+fn text(id: &str, content: &str, x: f64, y: f64, index: usize) -> Map<String, Value> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let chars = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "line and character counts are small"
+    )]
+    let rect = Rect {
+        x,
+        y,
+        width: chars as f64 * layout::CHAR_WIDTH,
+        height: lines.len() as f64 * LINE_HEIGHT,
+    };
+    let mut text = shape("text", id, &rect, index);
+    text.extend([
+        ("text".to_owned(), json!(content)),
+        ("originalText".to_owned(), json!(content)),
+        ("fontSize".to_owned(), json!(FONT_SIZE)),
+        ("fontFamily".to_owned(), json!(FONT_FAMILY)),
+        ("textAlign".to_owned(), json!("left")),
+        ("verticalAlign".to_owned(), json!("top")),
+        ("containerId".to_owned(), Value::Null),
+        ("lineHeight".to_owned(), json!(LINE_HEIGHT / FONT_SIZE)),
+        ("autoResize".to_owned(), json!(true)),
+    ]);
+    text
+}
+
+/// The properties every element has; `index` seeds the hand-drawn look reproducibly
+fn shape(kind: &str, id: &str, rect: &Rect, index: usize) -> Map<String, Value> {
+    [
+        ("id", json!(id)),
+        ("type", json!(kind)),
+        ("x", json!(rect.x)),
+        ("y", json!(rect.y)),
+        ("width", json!(rect.width)),
+        ("height", json!(rect.height)),
+        ("angle", json!(0)),
+        ("strokeColor", json!("#1e1e1e")),
+        ("backgroundColor", json!("transparent")),
+        ("fillStyle", json!("solid")),
+        ("strokeWidth", json!(1)),
+        ("strokeStyle", json!("solid")),
+        ("roughness", json!(0)),
+        ("opacity", json!(100)),
+        ("groupIds", json!([])),
+        ("frameId", Value::Null),
+        ("roundness", Value::Null),
+        ("seed", json!(index + 1)),
+        ("version", json!(1)),
+        ("versionNonce", json!(index + 1)),
+        ("isDeleted", json!(false)),
+        ("boundElements", Value::Null),
+        ("updated", json!(1)),
+        ("link", Value::Null),
+        ("locked", json!(false)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_owned(), value))
+    .collect()
+}
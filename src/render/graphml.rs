@@ -0,0 +1,194 @@
+//! `GraphML` output for graph analysis tools like yEd and Gephi.
+
+use std::fmt::Write as _;
+
+use crate::model::{Crate, Location, Module, RelationKind, Visibility, summary};
+
+/// Attributes of the nodes and edges: id, element, name and type
+const KEYS: [(&str, &str, &str, &str); 9] = [
+    ("label", "node", "label", "string"),
+    ("kind", "node", "kind", "string"),
+    ("description", "node", "description", "string"),
+    ("visibility", "node", "visibility", "string"),
+    ("module", "node", "module", "string"),
+    ("file", "node", "file", "string"),
+    ("line", "node", "line", "int"),
+    ("column", "node", "column", "int"),
+    ("relation", "edge", "relation", "string"),
+];
+
+/// A node of the graph, identified by the full path of its module or item
+struct Node<'a> {
+    id: String,
+    kind: &'static str,
+    name: &'a str,
+    visibility: Option<&'a Visibility>,
+    module: &'a Module,
+    location: Option<Location>,
+    doc: Option<&'a str>,
+}
+
+/// Render the crate as a `GraphML` graph of its modules and items.
+///
+/// Modules contain their submodules and items; structs and enums use the types of
+/// their fields and implement traits.
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let mut out = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">"#,
+        "\n"
+    ));
+    for (id, element, name, ty) in KEYS {
+        writeln!(
+            out,
+            r#"  <key id="{id}" for="{element}" attr.name="{name}" attr.type="{ty}"/>"#
+        )?;
+    }
+    writeln!(
+        out,
+        r#"  <graph id="{}" edgedefault="directed">"#,
+        escape(&krate.name)
+    )?;
+
+    let mut edges = Vec::new();
+    for module in krate.root.walk() {
+        for node in nodes(module) {
+            write_node(&mut out, &node)?;
+            if node.kind != "module" {
+                edges.push(("contains", module.path.clone(), node.id));
+            }
+        }
+        edges.extend(
+            module
+                .modules
+                .iter()
+                .map(|submodule| ("contains", module.path.clone(), submodule.path.clone())),
+        );
+    }
+    edges.extend(krate.relations.iter().map(|relation| {
+        let kind = match relation.kind {
+            RelationKind::Implements => "implements",
+            RelationKind::Uses => "uses",
+        };
+        (kind, relation.from.clone(), relation.to.clone())
+    }));
+
+    for (i, (kind, source, target)) in edges.iter().enumerate() {
+        writeln!(
+            out,
+            r#"    <edge id="e{i}" source="{}" target="{}"><data key="relation">{kind}</data></edge>"#,
+            escape(source),
+            escape(target)
+        )?;
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    Ok(out)
+}
+
+/// The module itself followed by the items declared directly in it
+fn nodes<'a>(module: &'a Module) -> Vec<Node<'a>> {
+    let node = |kind, name, visibility, location, doc: &'a Option<String>| Node {
+        id: match kind {
+            "module" => module.path.clone(),
+            // Functions live in another namespace than types and modules of the same name
+            "function" => format!("{}()", module.item_path(name)),
+            _ => module.item_path(name),
+        },
+        kind,
+        name,
+        visibility,
+        module,
+        location,
+        doc: doc.as_deref(),
+    };
+
+    std::iter::once(node("module", &module.name, None, None, &module.doc))
+        .chain(module.structs.iter().map(|s| {
+            node(
+                "struct",
+                &s.name,
+                Some(&s.visibility),
+                Some(s.location),
+                &s.doc,
+            )
+        }))
+        .chain(module.enums.iter().map(|e| {
+            node(
+                "enum",
+                &e.name,
+                Some(&e.visibility),
+                Some(e.location),
+                &e.doc,
+            )
+        }))
+        .chain(module.traits.iter().map(|t| {
+            node(
+                "trait",
+                &t.name,
+                Some(&t.visibility),
+                Some(t.location),
+                &t.doc,
+            )
+        }))
+        .chain(module.functions.iter().map(|f| {
+            node(
+                "function",
+                &f.name,
+                Some(&f.visibility),
+                Some(f.location),
+                &f.doc,
+            )
+        }))
+        .collect()
+}
+
+fn write_node(out: &mut String, node: &Node<'_>) -> anyhow::Result<()> {
+    write!(out, r#"    <node id="{}">"#, escape(&node.id))?;
+    write!(out, r#"<data key="label">{}</data>"#, escape(node.name))?;
+    write!(out, r#"<data key="kind">{}</data>"#, node.kind)?;
+    if let Some(summary) = summary(node.doc) {
+        write!(
+            out,
+            r#"<data key="description">{}</data>"#,
+            escape(&summary)
+        )?;
+    }
+    if let Some(visibility) = node.visibility {
+        write!(
+            out,
+            r#"<data key="visibility">{}</data>"#,
+            escape(&visibility.to_string())
+        )?;
+    }
+    write!(
+        out,
+        r#"<data key="module">{}</data>"#,
+        escape(&node.module.path)
+    )?;
+    if let Some(file) = &node.module.file {
+        write!(
+            out,
+            r#"<data key="file">{}</data>"#,
+            escape(&file.display().to_string())
+        )?;
+    }
+    if let Some(location) = node.location {
+        write!(
+            out,
+            r#"<data key="line">{}</data><data key="column">{}</data>"#,
+            location.line, location.column
+        )?;
+    }
+    out.push_str("</node>\n");
+    Ok(())
+}
+
+/// Escape text for XML content and attributes
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,229 @@
+use anyhow::Context as _;
+use serde_json::json;
+use tinytemplate::TinyTemplate;
+
+use crate::{
+    items::{
+        ToHtml as _, declarations::DeclarationContext, doc_html, enums::EnumContext,
+        extern_blocks::ExternBlockContext, functions::FunctionContext, impl_blocks::ImplContext,
+        macros::MacroContext, members::TypeMembers, module::ModContext, structs::StructContext,
+        traits::TraitContext, unions::UnionContext,
+    },
+    model::{Crate, Impl, Module},
+};
+
+const HTML_TEMPLATE: &str = r#"
+<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>{title}</title>
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <link rel="stylesheet" href="diagram.css">
+</head>
+<style>{style_contents}</style>
+<body>
+    <header>
+        <h1>{title}</h1>
+        <div class="controls">
+            <p class="instruction">Click module names to collapse/expand</p>
+        </div>
+    </header>
+
+    <main id="content">
+        {contents}
+    </main>
+</body>
+</html>
+"#;
+
+/// Render the crate as a standalone HTML page.
+///
+/// With `group_methods` the inherent impl blocks of structs and enums are shown on their
+/// cards, along with the traits they implement.
+pub fn render(krate: &Crate, title: &str, group_methods: bool) -> anyhow::Result<String> {
+    let members = group_methods.then(|| TypeMembers::new(krate));
+    let contents = doc_html(krate.root.doc.as_deref()).unwrap_or_default()
+        + &organize_and_render_items(&krate.root, members.as_ref())?;
+
+    let mut tt = TinyTemplate::new();
+    tt.set_default_formatter(&tinytemplate::format_unescaped);
+    tt.add_template("html", HTML_TEMPLATE)
+        .context("Failed to add template")?;
+    tt.render(
+        "html",
+        &json!({
+            "title": title,
+            "contents": contents,
+            "style_contents": include_str!("../style.css")
+        }),
+    )
+    .context("Failed to render template")
+}
+
+/// Render the items of a module grouped by type, followed by its submodules
+pub fn organize_and_render_items(
+    module: &Module,
+    members: Option<&TypeMembers<'_>>,
+) -> anyhow::Result<String> {
+    let mut result = String::new();
+
+    // Helper function to create a section with items
+    let create_section = |section_name: &str, items: Vec<String>, grid_class: &str| -> String {
+        let items = items
+            .into_iter()
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return String::new();
+        }
+
+        let mut section_html = format!(
+            r#"<div class="item-section">
+    <div class="item-section-header">{section_name}</div>
+    <div class="{grid_class}">
+"#
+        );
+
+        for item in items {
+            section_html.push_str(&item);
+        }
+
+        section_html.push_str("    </div>\n</div>\n\n");
+        section_html
+    };
+
+    let structs = module
+        .structs
+        .iter()
+        .map(|s| {
+            let mut context = StructContext::from(s);
+            if let Some(members) = members {
+                context.members = members.context(&module.item_path(&s.name))?;
+            }
+            context.to_html()
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let unions = module
+        .unions
+        .iter()
+        .map(|u| UnionContext::from(u).to_html())
+        .collect::<anyhow::Result<_>>()?;
+    let enums = module
+        .enums
+        .iter()
+        .map(|e| {
+            let mut context = EnumContext::from(e);
+            if let Some(members) = members {
+                context.members = members.context(&module.item_path(&e.name))?;
+            }
+            context.to_html()
+        })
+        .collect::<anyhow::Result<_>>()?;
+    let traits = module
+        .traits
+        .iter()
+        .map(|t| TraitContext::new(t)?.to_html())
+        .collect::<anyhow::Result<_>>()?;
+    let type_aliases = declarations(&module.type_aliases)?;
+    let consts = declarations(&module.consts)?;
+    let statics = declarations(&module.statics)?;
+    let functions = module
+        .functions
+        .iter()
+        .map(|f| FunctionContext::new(f).to_html())
+        .collect::<anyhow::Result<_>>()?;
+    let macros = module
+        .macros
+        .iter()
+        .map(|m| MacroContext::from(m).to_html())
+        .collect::<anyhow::Result<_>>()?;
+    let extern_blocks = module
+        .extern_blocks
+        .iter()
+        .map(|block| ExternBlockContext::new(block)?.to_html())
+        .collect::<anyhow::Result<_>>()?;
+    let impls = module
+        .impls
+        .iter()
+        .filter(|imp| members.is_none_or(|members| !members.owns(imp)))
+        .map(render_impl)
+        .collect::<anyhow::Result<_>>()?;
+
+    // Render sections in organized order
+    result.push_str(&create_section("Structs", structs, "structs-grid"));
+    result.push_str(&create_section("Unions", unions, "unions-grid"));
+    result.push_str(&create_section("Enums", enums, "enums-grid"));
+    result.push_str(&create_section("Traits", traits, "traits-grid"));
+    result.push_str(&create_section(
+        "Type Aliases",
+        type_aliases,
+        "declarations-grid",
+    ));
+    result.push_str(&create_section("Constants", consts, "declarations-grid"));
+    result.push_str(&create_section("Statics", statics, "declarations-grid"));
+    result.push_str(&create_section("Functions", functions, "functions-grid"));
+    result.push_str(&create_section("Macros", macros, "macros-grid"));
+    result.push_str(&create_section("FFI", extern_blocks, "extern-blocks-grid"));
+    result.push_str(&create_section(
+        "Implementations",
+        impls,
+        "impl-blocks-grid",
+    ));
+
+    // Modules get special treatment - they don't need a section wrapper
+    for submodule in &module.modules {
+        result.push_str(&render_module(submodule, members)?);
+    }
+
+    Ok(result)
+}
+
+fn declarations<'a, T>(items: &'a [T]) -> anyhow::Result<Vec<String>>
+where
+    DeclarationContext: From<&'a T>,
+{
+    items
+        .iter()
+        .map(|item| DeclarationContext::from(item).to_html())
+        .collect()
+}
+
+fn render_impl(imp: &Impl) -> anyhow::Result<String> {
+    // Only create impl block if it has items to display
+    if imp.is_empty() {
+        return Ok(String::new());
+    }
+
+    let functions = imp
+        .functions
+        .iter()
+        .map(|f| FunctionContext::new(f).to_html())
+        .collect::<anyhow::Result<_>>()?;
+
+    ImplContext {
+        target_type: imp.self_ty.clone(),
+        trait_name: imp.trait_name.clone(),
+        generics: imp.generics.clone(),
+        where_clause: imp.where_clause.clone(),
+        associated_items: imp.associated_items().collect(),
+        functions,
+    }
+    .to_html()
+}
+
+fn render_module(module: &Module, members: Option<&TypeMembers<'_>>) -> anyhow::Result<String> {
+    let contents = organize_and_render_items(module, members)?;
+
+    // Don't render empty modules
+    if contents.trim().is_empty() {
+        return Ok(String::new());
+    }
+
+    ModContext {
+        name: module.name.clone(),
+        doc: doc_html(module.doc.as_deref()),
+        contents,
+    }
+    .to_html()
+}
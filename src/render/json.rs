@@ -0,0 +1,29 @@
+//! JSON export of the crate model.
+//!
+//! `crate` is the serialized [`Crate`], the layout is documented in `docs/json-schema.md`.
+
+use anyhow::Context as _;
+use serde::Serialize;
+
+use crate::model::Crate;
+
+/// Version of the JSON document layout
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: u32,
+    generator: String,
+    #[serde(rename = "crate")]
+    krate: &'a Crate,
+}
+
+/// Render the crate as a pretty-printed JSON document
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    serde_json::to_string_pretty(&Document {
+        schema_version: SCHEMA_VERSION,
+        generator: concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).to_owned(),
+        krate,
+    })
+    .context("Failed to serialize crate")
+}
@@ -0,0 +1,521 @@
+//! A simple layered layout for drawing the crate without external tools.
+//!
+//! Every module becomes a frame. Inside a frame the struct and enum cards are put
+//! into layers so that a type is placed below the types that use it, followed by rows
+//! of function and impl cards and finally the frames of the submodules. Relations are
+//! routed as orthogonal edges between the cards.
+
+use itertools::Itertools as _;
+use std::{cmp::Reverse, collections::HashMap};
+
+use super::uml_visibility;
+use crate::model::{Crate, Function, Module, RelationKind};
+
+/// Approximate width of a character of the monospace font
+pub const CHAR_WIDTH: f64 = 7.2;
+/// Height of a line of text
+pub const LINE_HEIGHT: f64 = 16.0;
+/// Space between a border and its text
+pub const PADDING: f64 = 8.0;
+/// Height of the header of a frame
+pub const FRAME_HEADER: f64 = 28.0;
+/// Space between cards and frames
+const GAP: f64 = 24.0;
+/// Rows of cards and frames wrap once they get wider than this
+const MAX_ROW_WIDTH: f64 = 1400.0;
+/// Longer lines are truncated
+const MAX_LINE_CHARS: usize = 72;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub const fn center_x(&self) -> f64 {
+        self.x + self.width / 2.0
+    }
+
+    pub const fn center_y(&self) -> f64 {
+        self.y + self.height / 2.0
+    }
+
+    pub const fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub const fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// Whether `other` lies entirely inside this rectangle
+    pub const fn contains(&self, other: &Self) -> bool {
+        self.x <= other.x
+            && self.y <= other.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    const fn translate(mut self, dx: f64, dy: f64) -> Self {
+        self.x += dx;
+        self.y += dy;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardKind {
+    Struct,
+    Enum,
+    Trait,
+    Function,
+    Impl,
+}
+
+/// A module
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub name: String,
+    pub path: String,
+    pub rect: Rect,
+}
+
+/// An item drawn as a box with a title and compartments of text lines
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub kind: CardKind,
+    /// Full path of the type, `None` for functions and impl blocks
+    pub path: Option<String>,
+    pub title: String,
+    pub compartments: Vec<Vec<String>>,
+    pub rect: Rect,
+}
+
+impl Card {
+    fn new(
+        kind: CardKind,
+        path: Option<String>,
+        title: String,
+        compartments: Vec<Vec<String>>,
+    ) -> Self {
+        let title = truncate(title);
+        let compartments = compartments
+            .into_iter()
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| lines.into_iter().map(truncate).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let chars = compartments
+            .iter()
+            .flatten()
+            .chain(std::iter::once(&title))
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let lines = compartments.iter().map(Vec::len).sum::<usize>();
+
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "line and character counts are small"
+        )]
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: (chars as f64).mul_add(CHAR_WIDTH, 2.0 * PADDING),
+            // The title and every compartment are padded on both sides
+            height: (2.0 * PADDING).mul_add(
+                (compartments.len() + 1) as f64,
+                ((lines + 1) as f64) * LINE_HEIGHT,
+            ),
+        };
+
+        Self {
+            kind,
+            path,
+            title,
+            compartments,
+            rect,
+        }
+    }
+}
+
+/// A relation routed as a polyline from the source to the target card
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub kind: RelationKind,
+    /// Indices of the source and target in [`Layout::cards`]
+    pub from: usize,
+    pub to: usize,
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    pub width: f64,
+    pub height: f64,
+    pub frames: Vec<Frame>,
+    pub cards: Vec<Card>,
+    pub edges: Vec<Edge>,
+}
+
+/// Lay out the modules, items and relations of the crate
+pub fn layout(krate: &Crate) -> Layout {
+    let mut layout = layout_module(krate, &krate.root).unwrap_or_default();
+    if let Some(root) = layout.frames.first_mut() {
+        root.name.clone_from(&krate.name);
+    }
+
+    let cards = layout
+        .cards
+        .iter()
+        .enumerate()
+        .filter_map(|(i, card)| card.path.as_deref().map(|path| (path, i)))
+        .collect::<HashMap<_, _>>();
+    layout.edges = krate
+        .relations
+        .iter()
+        .filter_map(|relation| {
+            let from = *cards.get(relation.from.as_str())?;
+            let to = *cards.get(relation.to.as_str())?;
+            Some(Edge {
+                kind: relation.kind,
+                from,
+                to,
+                points: route(&layout.cards[from].rect, &layout.cards[to].rect),
+            })
+        })
+        .collect();
+
+    layout
+}
+
+/// Lay out a module at the origin, `None` if there is nothing to show
+fn layout_module(krate: &Crate, module: &Module) -> Option<Layout> {
+    let type_cards = layer_types(krate, module);
+    let function_cards = module
+        .functions
+        .iter()
+        .map(|f| Card::new(CardKind::Function, None, f.signature(), Vec::new()))
+        .collect::<Vec<_>>();
+    let impl_cards = module
+        .impls
+        .iter()
+        .filter(|imp| !imp.is_empty())
+        .map(|imp| {
+            let compartments = [
+                imp.associated_items().collect::<Vec<_>>(),
+                imp.functions.iter().map(Function::signature).collect(),
+            ];
+            Card::new(
+                CardKind::Impl,
+                None,
+                imp.header(),
+                compartments
+                    .into_iter()
+                    .filter(|lines| !lines.is_empty())
+                    .collect(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let submodules = module
+        .modules
+        .iter()
+        .filter_map(|m| layout_module(krate, m))
+        .collect::<Vec<_>>();
+
+    if type_cards.is_empty()
+        && function_cards.is_empty()
+        && impl_cards.is_empty()
+        && submodules.is_empty()
+    {
+        return None;
+    }
+
+    let mut layout = Layout::default();
+    let mut y = FRAME_HEADER + PADDING;
+    let mut width: f64 = 0.0;
+
+    let card_rows = type_cards
+        .into_iter()
+        .chain([function_cards, impl_cards])
+        .flat_map(|row| wrap(row, |card| card.rect.width));
+    for row in card_rows {
+        let (row_width, row_height) = place_row(row.iter().map(|card| card.rect), y, |i, rect| {
+            layout.cards.push(Card {
+                rect,
+                ..row[i].clone()
+            });
+        });
+        width = width.max(row_width);
+        y += row_height + GAP;
+    }
+
+    for row in wrap(submodules, |sub| sub.width) {
+        let (row_width, row_height) = place_row(
+            row.iter().map(|sub| Rect {
+                width: sub.width,
+                height: sub.height,
+                ..Rect::default()
+            }),
+            y,
+            |i, rect| {
+                let sub = &row[i];
+                layout.frames.extend(sub.frames.iter().map(|frame| Frame {
+                    rect: frame.rect.translate(rect.x, rect.y),
+                    ..frame.clone()
+                }));
+                layout.cards.extend(sub.cards.iter().map(|card| Card {
+                    rect: card.rect.translate(rect.x, rect.y),
+                    ..card.clone()
+                }));
+            },
+        );
+        width = width.max(row_width);
+        y += row_height + GAP;
+    }
+
+    #[expect(clippy::cast_precision_loss, reason = "module names are short")]
+    let name_width = module.name.len() as f64 * CHAR_WIDTH;
+    layout.width = 2.0f64.mul_add(PADDING, width.max(name_width));
+    layout.height = y - GAP + PADDING;
+    layout.frames.insert(
+        0,
+        Frame {
+            name: module.name.clone(),
+            path: module.path.clone(),
+            rect: Rect {
+                x: 0.0,
+                y: 0.0,
+                width: layout.width,
+                height: layout.height,
+            },
+        },
+    );
+
+    Some(layout)
+}
+
+/// Struct, enum and trait cards of the module in layers, each type below the types using it
+fn layer_types(krate: &Crate, module: &Module) -> Vec<Vec<Card>> {
+    let cards = type_cards(module);
+
+    let index = cards
+        .iter()
+        .enumerate()
+        .filter_map(|(i, card)| card.path.clone().map(|path| (path, i)))
+        .collect::<HashMap<_, _>>();
+    let edges = krate
+        .relations
+        .iter()
+        .filter_map(|r| Some((*index.get(&r.from)?, *index.get(&r.to)?)))
+        .filter(|(from, to)| from != to)
+        .collect::<Vec<_>>();
+
+    // Longest path layering; the number of rounds is bounded so cycles terminate
+    let mut layers = vec![0_usize; cards.len()];
+    for _ in 0..cards.len() {
+        let mut changed = false;
+        for &(from, to) in &edges {
+            if layers[to] <= layers[from] && layers[from] + 1 < cards.len() {
+                layers[to] = layers[from] + 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Order each layer by the average position of the cards pointing at it
+    let mut position = vec![0.0_f64; cards.len()];
+    let mut rows: Vec<Vec<usize>> = Vec::new();
+    for layer in 0..=layers.iter().copied().max().unwrap_or_default() {
+        let mut row = (0..cards.len())
+            .filter(|&i| layers[i] == layer)
+            .collect::<Vec<_>>();
+        let barycenter = |i: usize| {
+            let sources = edges
+                .iter()
+                .filter(|&&(from, to)| to == i && layers[from] < layer)
+                .map(|&(from, _)| position[from])
+                .collect::<Vec<_>>();
+            #[expect(clippy::cast_precision_loss, reason = "edge counts are small")]
+            if sources.is_empty() {
+                f64::MAX
+            } else {
+                sources.iter().sum::<f64>() / sources.len() as f64
+            }
+        };
+        row.sort_by(|&a, &b| barycenter(a).total_cmp(&barycenter(b)));
+        for (pos, &i) in row.iter().enumerate() {
+            #[expect(clippy::cast_precision_loss, reason = "row lengths are small")]
+            {
+                position[i] = pos as f64;
+            }
+        }
+        rows.push(row);
+    }
+
+    let mut slots = cards.into_iter().map(Some).collect::<Vec<_>>();
+    rows.into_iter()
+        .map(|row| row.into_iter().filter_map(|i| slots[i].take()).collect())
+        .filter(|row: &Vec<Card>| !row.is_empty())
+        .collect()
+}
+
+/// A card for every struct, enum and trait of the module
+fn type_cards(module: &Module) -> Vec<Card> {
+    module
+        .structs
+        .iter()
+        .map(|s| {
+            let fields = s
+                .fields
+                .iter()
+                .enumerate()
+                .sorted_by_key(|(_, f)| Reverse(f.visibility.level()))
+                .map(|(i, f)| {
+                    format!(
+                        "{} {}: {}",
+                        uml_visibility(&f.visibility),
+                        f.display_name(i),
+                        f.ty
+                    )
+                })
+                .collect();
+            Card::new(
+                CardKind::Struct,
+                Some(module.item_path(&s.name)),
+                format!("{}{}", s.name, s.generics.as_deref().unwrap_or_default()),
+                vec![fields],
+            )
+        })
+        .chain(module.enums.iter().map(|e| {
+            Card::new(
+                CardKind::Enum,
+                Some(module.item_path(&e.name)),
+                format!("{}{}", e.name, e.generics.as_deref().unwrap_or_default()),
+                vec![e.variants.iter().map(|v| format!("• {v}")).collect()],
+            )
+        }))
+        .chain(module.traits.iter().map(|t| {
+            Card::new(
+                CardKind::Trait,
+                Some(module.item_path(&t.name)),
+                format!(
+                    "{}{}{}",
+                    t.name,
+                    t.generics.as_deref().unwrap_or_default(),
+                    t.supertraits().unwrap_or_default()
+                ),
+                vec![
+                    t.types
+                        .iter()
+                        .map(ToString::to_string)
+                        .chain(t.consts.iter().map(ToString::to_string))
+                        .collect(),
+                    t.required_methods.iter().map(Function::signature).collect(),
+                    t.provided_methods
+                        .iter()
+                        .map(|f| format!("{} {{ … }}", f.signature()))
+                        .collect(),
+                ],
+            )
+        }))
+        .collect()
+}
+
+/// Split a row of elements into rows no wider than [`MAX_ROW_WIDTH`]
+fn wrap<T>(elements: Vec<T>, width: impl Fn(&T) -> f64) -> Vec<Vec<T>> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+    let mut row_width = 0.0;
+    for element in elements {
+        let element_width = width(&element);
+        match rows.last_mut() {
+            Some(row) if row_width + element_width <= MAX_ROW_WIDTH => {
+                row.push(element);
+                row_width += element_width + GAP;
+            }
+            _ => {
+                rows.push(vec![element]);
+                row_width = element_width + GAP;
+            }
+        }
+    }
+    rows
+}
+
+/// Place rectangles left to right at height `y`, returns the size of the row
+fn place_row(
+    rects: impl Iterator<Item = Rect>,
+    y: f64,
+    mut place: impl FnMut(usize, Rect),
+) -> (f64, f64) {
+    let mut x = PADDING;
+    let mut height: f64 = 0.0;
+    for (i, rect) in rects.enumerate() {
+        place(
+            i,
+            Rect {
+                x,
+                y,
+                width: rect.width,
+                height: rect.height,
+            },
+        );
+        x += rect.width + GAP;
+        height = height.max(rect.height);
+    }
+    (x - GAP - PADDING, height)
+}
+
+/// Route an edge orthogonally between the facing sides of two cards
+fn route(from: &Rect, to: &Rect) -> Vec<(f64, f64)> {
+    if to.y >= from.bottom() {
+        let mid = f64::midpoint(from.bottom(), to.y);
+        vec![
+            (from.center_x(), from.bottom()),
+            (from.center_x(), mid),
+            (to.center_x(), mid),
+            (to.center_x(), to.y),
+        ]
+    } else if to.bottom() <= from.y {
+        let mid = f64::midpoint(from.y, to.bottom());
+        vec![
+            (from.center_x(), from.y),
+            (from.center_x(), mid),
+            (to.center_x(), mid),
+            (to.center_x(), to.bottom()),
+        ]
+    } else if to.x >= from.right() {
+        let mid = f64::midpoint(from.right(), to.x);
+        vec![
+            (from.right(), from.center_y()),
+            (mid, from.center_y()),
+            (mid, to.center_y()),
+            (to.x, to.center_y()),
+        ]
+    } else {
+        let mid = f64::midpoint(from.x, to.right());
+        vec![
+            (from.x, from.center_y()),
+            (mid, from.center_y()),
+            (mid, to.center_y()),
+            (to.right(), to.center_y()),
+        ]
+    }
+}
+
+fn truncate(line: String) -> String {
+    if line.chars().count() > MAX_LINE_CHARS {
+        line.chars()
+            .take(MAX_LINE_CHARS - 1)
+            .chain(std::iter::once('…'))
+            .collect()
+    } else {
+        line
+    }
+}
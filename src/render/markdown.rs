@@ -0,0 +1,462 @@
+//! Markdown API report with one section per module.
+
+use std::fmt::Write as _;
+
+use crate::model::{Crate, Field, Module, abbreviate, summary};
+
+/// Render the crate as a markdown report of its modules and items
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let mut out = format!("# {}\n", krate.name);
+
+    for module in krate.root.walk() {
+        write_module(&mut out, module)?;
+    }
+
+    Ok(out)
+}
+
+fn write_module(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    let impls = module
+        .impls
+        .iter()
+        .filter(|imp| !imp.is_empty())
+        .collect::<Vec<_>>();
+    if module.structs.is_empty()
+        && module.unions.is_empty()
+        && module.enums.is_empty()
+        && module.traits.is_empty()
+        && module.type_aliases.is_empty()
+        && module.consts.is_empty()
+        && module.statics.is_empty()
+        && module.functions.is_empty()
+        && module.macros.is_empty()
+        && module.extern_blocks.is_empty()
+        && impls.is_empty()
+    {
+        return Ok(());
+    }
+
+    writeln!(out, "\n## `{}`", module.path)?;
+    if let Some(doc) = &module.doc {
+        write!(out, "\n{}", quote(doc))?;
+    }
+    write_structs(out, module)?;
+    write_unions(out, module)?;
+    write_enums(out, module)?;
+    write_traits(out, module)?;
+    write_declarations(out, module)?;
+
+    if !module.functions.is_empty() {
+        writeln!(out, "\n### Functions\n")?;
+        let rows = module
+            .functions
+            .iter()
+            .map(|f| {
+                (
+                    vec![
+                        format!("`{}`", f.name),
+                        format!("`{}`", cell(&f.signature())),
+                    ],
+                    f.doc.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        write_table(out, &["Function", "Signature"], &rows)?;
+    }
+
+    write_macros(out, module)?;
+    write_extern_blocks(out, module)?;
+
+    if !impls.is_empty() {
+        writeln!(out, "\n### Implementations\n")?;
+    }
+    for imp in impls {
+        writeln!(out, "- `{}`", imp.header())?;
+        for item in imp.associated_items() {
+            writeln!(out, "  - `{item}`")?;
+        }
+        for f in &imp.functions {
+            writeln!(out, "  - `{}`", f.signature())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_structs(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.structs.is_empty() {
+        writeln!(out, "\n### Structs")?;
+    }
+    for s in &module.structs {
+        writeln!(
+            out,
+            "\n#### `{}{}`\n",
+            s.name,
+            s.generics.as_deref().unwrap_or_default()
+        )?;
+        write_attributes(out, &s.derives, &s.attributes)?;
+        write_doc(out, s.doc.as_deref())?;
+        write_fields(out, &s.fields)?;
+    }
+
+    Ok(())
+}
+
+fn write_unions(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.unions.is_empty() {
+        writeln!(out, "\n### Unions")?;
+    }
+    for u in &module.unions {
+        writeln!(
+            out,
+            "\n#### `{}{}`\n",
+            u.name,
+            u.generics.as_deref().unwrap_or_default()
+        )?;
+        write_doc(out, u.doc.as_deref())?;
+        write_fields(out, &u.fields)?;
+    }
+
+    Ok(())
+}
+
+fn write_fields(out: &mut String, fields: &[Field]) -> anyhow::Result<()> {
+    if fields.is_empty() {
+        writeln!(out, "No fields.")?;
+        return Ok(());
+    }
+    let rows = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            (
+                vec![
+                    format!("`{}`", field.display_name(i)),
+                    format!("`{}`", cell(&field.ty)),
+                    field.visibility.to_string(),
+                ],
+                field.doc.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    write_table(out, &["Field", "Type", "Visibility"], &rows)
+}
+
+fn write_enums(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.enums.is_empty() {
+        writeln!(out, "\n### Enums")?;
+    }
+    for e in &module.enums {
+        writeln!(
+            out,
+            "\n#### `{}{}`\n",
+            e.name,
+            e.generics.as_deref().unwrap_or_default()
+        )?;
+        write_attributes(out, &e.derives, &e.attributes)?;
+        write_doc(out, e.doc.as_deref())?;
+        if e.variants.is_empty() {
+            writeln!(out, "No variants.")?;
+            continue;
+        }
+        // Discriminants and attributes only get a column when some variant has them
+        let discriminants = e.variants.iter().any(|v| v.discriminant.is_some());
+        let attributes = e.variants.iter().any(|v| !v.attributes.is_empty());
+        let rows = e
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut cells = vec![
+                    format!("`{}`", variant.name),
+                    variant
+                        .data()
+                        .map(|data| format!("`{}`", cell(&data)))
+                        .unwrap_or_default(),
+                ];
+                if discriminants {
+                    cells.push(
+                        variant
+                            .discriminant
+                            .as_ref()
+                            .map(|value| format!("`{}`", cell(value)))
+                            .unwrap_or_default(),
+                    );
+                }
+                if attributes {
+                    cells.push(
+                        variant
+                            .attributes
+                            .iter()
+                            .map(|attribute| format!("`#[{}]`", cell(attribute)))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    );
+                }
+                (cells, variant.doc.as_deref())
+            })
+            .collect::<Vec<_>>();
+        let header = ["Variant", "Data"]
+            .into_iter()
+            .chain(discriminants.then_some("Value"))
+            .chain(attributes.then_some("Attributes"))
+            .collect::<Vec<_>>();
+        write_table(out, &header, &rows)?;
+    }
+
+    Ok(())
+}
+
+fn write_traits(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.traits.is_empty() {
+        writeln!(out, "\n### Traits")?;
+    }
+    for t in &module.traits {
+        writeln!(
+            out,
+            "\n#### `{}{}{}`\n",
+            t.name,
+            t.generics.as_deref().unwrap_or_default(),
+            t.supertraits().unwrap_or_default()
+        )?;
+        write_doc(out, t.doc.as_deref())?;
+        let items = t
+            .types
+            .iter()
+            .map(|ty| ("associated type", ty.to_string(), None))
+            .chain(
+                t.consts
+                    .iter()
+                    .map(|c| ("associated const", c.to_string(), None)),
+            )
+            .chain(
+                t.required_methods
+                    .iter()
+                    .map(|f| ("required method", f.signature(), f.doc.as_deref())),
+            )
+            .chain(
+                t.provided_methods
+                    .iter()
+                    .map(|f| ("provided method", f.signature(), f.doc.as_deref())),
+            )
+            .map(|(kind, declaration, doc)| {
+                (
+                    vec![kind.to_owned(), format!("`{}`", cell(&declaration))],
+                    doc,
+                )
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            writeln!(out, "No items.")?;
+            continue;
+        }
+        write_table(out, &["Item", "Declaration"], &items)?;
+    }
+
+    Ok(())
+}
+
+fn write_declarations(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.type_aliases.is_empty() {
+        writeln!(out, "\n### Type Aliases\n")?;
+        let rows = module
+            .type_aliases
+            .iter()
+            .map(|alias| {
+                (
+                    vec![
+                        format!(
+                            "`{}{}`",
+                            alias.name,
+                            cell(alias.generics.as_deref().unwrap_or_default())
+                        ),
+                        format!("`{}`", cell(&alias.ty)),
+                    ],
+                    alias.doc.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        write_table(out, &["Alias", "Type"], &rows)?;
+    }
+
+    if !module.consts.is_empty() {
+        writeln!(out, "\n### Constants\n")?;
+        let rows = module
+            .consts
+            .iter()
+            .map(|c| {
+                (
+                    vec![
+                        format!("`{}`", c.name),
+                        format!("`{}`", cell(&c.ty)),
+                        format!("`{}`", cell(&abbreviate(&c.value))),
+                    ],
+                    c.doc.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        write_table(out, &["Constant", "Type", "Value"], &rows)?;
+    }
+
+    if !module.statics.is_empty() {
+        writeln!(out, "\n### Statics\n")?;
+        let rows = module
+            .statics
+            .iter()
+            .map(|s| {
+                (
+                    vec![
+                        format!("`{}`", s.name),
+                        format!("`{}`", cell(&s.ty)),
+                        format!("`{}`", cell(&abbreviate(&s.value))),
+                        (if s.is_mut { "yes" } else { "no" }).to_owned(),
+                    ],
+                    s.doc.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        write_table(out, &["Static", "Type", "Value", "Mutable"], &rows)?;
+    }
+
+    Ok(())
+}
+
+fn write_macros(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.macros.is_empty() {
+        writeln!(out, "\n### Macros")?;
+    }
+    for m in &module.macros {
+        writeln!(out, "\n#### `{}!`\n", m.name)?;
+        if m.exported {
+            writeln!(out, "Exported with `#[macro_export]`.\n")?;
+        }
+        write_doc(out, m.doc.as_deref())?;
+        for arm in &m.arms {
+            writeln!(out, "- `{arm}`")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_extern_blocks(out: &mut String, module: &Module) -> anyhow::Result<()> {
+    if !module.extern_blocks.is_empty() {
+        writeln!(out, "\n### FFI")?;
+    }
+    for block in &module.extern_blocks {
+        writeln!(out, "\n#### `extern \"{}\"`\n", block.abi)?;
+        let items = block
+            .types
+            .iter()
+            .map(|ty| ("type", format!("type {ty}"), None))
+            .chain(
+                block
+                    .statics
+                    .iter()
+                    .map(|s| ("static", s.to_string(), None)),
+            )
+            .chain(
+                block
+                    .functions
+                    .iter()
+                    .map(|f| ("function", f.signature(), f.doc.as_deref())),
+            )
+            .map(|(kind, declaration, doc)| {
+                (
+                    vec![kind.to_owned(), format!("`{}`", cell(&declaration))],
+                    doc,
+                )
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            writeln!(out, "No items.")?;
+            continue;
+        }
+        write_table(out, &["Item", "Declaration"], &items)?;
+    }
+
+    Ok(())
+}
+
+/// A table of already formatted cells, with a description column for the first paragraph
+/// of the doc comments when any of the rows is documented
+fn write_table(
+    out: &mut String,
+    header: &[&str],
+    rows: &[(Vec<String>, Option<&str>)],
+) -> anyhow::Result<()> {
+    let documented = rows.iter().any(|(_, doc)| doc.is_some());
+    let header = header
+        .iter()
+        .copied()
+        .chain(documented.then_some("Description"))
+        .collect::<Vec<_>>();
+    writeln!(out, "| {} |", header.join(" | "))?;
+    writeln!(
+        out,
+        "| {} |",
+        header
+            .iter()
+            .map(|title| "-".repeat(title.len()))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    )?;
+    for (cells, doc) in rows {
+        let description = documented.then(|| cell(&summary(*doc).unwrap_or_default()));
+        writeln!(
+            out,
+            "| {} |",
+            cells
+                .iter()
+                .cloned()
+                .chain(description)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The derives and attributes of an item as a line of code spans below its heading
+fn write_attributes(
+    out: &mut String,
+    derives: &[String],
+    attributes: &[String],
+) -> anyhow::Result<()> {
+    let derive = (!derives.is_empty()).then(|| format!("derive({})", derives.join(", ")));
+    let attributes = derive
+        .iter()
+        .chain(attributes)
+        .map(|attribute| format!("`#[{attribute}]`"))
+        .collect::<Vec<_>>();
+    if !attributes.is_empty() {
+        writeln!(out, "{}\n", attributes.join(" "))?;
+    }
+    Ok(())
+}
+
+/// The whole doc comment of an item as a blockquote below its heading
+fn write_doc(out: &mut String, doc: Option<&str>) -> anyhow::Result<()> {
+    if let Some(doc) = doc {
+        writeln!(out, "{}", quote(doc))?;
+    }
+    Ok(())
+}
+
+/// A doc comment as blockquote lines
+fn quote(doc: &str) -> String {
+    doc.trim().lines().fold(String::new(), |mut out, line| {
+        out.push('>');
+        if !line.is_empty() {
+            out.push(' ');
+            out.push_str(line);
+        }
+        out.push('\n');
+        out
+    })
+}
+
+/// Escape pipes, which end a table cell even inside code spans
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
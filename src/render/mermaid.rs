@@ -0,0 +1,168 @@
+//! Mermaid `classDiagram` output, renderable by GitHub and GitLab markdown.
+
+use itertools::Itertools as _;
+use std::{cmp::Reverse, fmt::Write as _};
+
+use super::{generic_names, methods_by_type, type_ids, type_summaries, uml_visibility};
+use crate::model::{Crate, Function, RelationKind};
+
+/// Render the crate as a Mermaid class diagram with one namespace per module
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let ids = type_ids(krate, "_");
+    let methods = methods_by_type(krate);
+    let mut out = String::from("classDiagram\n");
+
+    for module in krate.root.walk() {
+        if module.type_names().next().is_none() {
+            continue;
+        }
+
+        writeln!(out, "    namespace {} {{", module.path.replace("::", "_"))?;
+
+        for s in &module.structs {
+            let path = module.item_path(&s.name);
+            let fields = s
+                .fields
+                .iter()
+                .enumerate()
+                .sorted_by_key(|(_, f)| Reverse(f.visibility.level()))
+                .map(|(i, f)| {
+                    format!(
+                        "{}{}: {}",
+                        uml_visibility(&f.visibility),
+                        f.display_name(i),
+                        escape(&f.ty)
+                    )
+                });
+            let members = fields
+                .chain(method_lines(methods.get(path.as_str())))
+                .collect::<Vec<_>>();
+
+            write_class(&mut out, &ids[&path], s.generics.as_deref(), &members)?;
+        }
+
+        for e in &module.enums {
+            let path = module.item_path(&e.name);
+            let members = std::iter::once("<<enumeration>>".to_owned())
+                .chain(e.variants.iter().map(|v| escape(&v.to_string())))
+                .chain(method_lines(methods.get(path.as_str())))
+                .collect::<Vec<_>>();
+
+            write_class(&mut out, &ids[&path], e.generics.as_deref(), &members)?;
+        }
+
+        for t in &module.traits {
+            let path = module.item_path(&t.name);
+            let required = t.required_methods.iter().collect::<Vec<_>>();
+            let provided = t.provided_methods.iter().collect::<Vec<_>>();
+            let members = std::iter::once("<<interface>>".to_owned())
+                .chain(t.types.iter().map(|ty| escape(&ty.to_string())))
+                .chain(t.consts.iter().map(|c| escape(&c.to_string())))
+                // `*` marks abstract methods, Mermaid expects it before `$` and the return type
+                .chain(method_lines(Some(&required)).map(|line| abstract_method(&line)))
+                .chain(method_lines(Some(&provided)))
+                .chain(method_lines(methods.get(path.as_str())))
+                .collect::<Vec<_>>();
+
+            write_class(&mut out, &ids[&path], t.generics.as_deref(), &members)?;
+        }
+
+        writeln!(out, "    }}")?;
+    }
+
+    for relation in &krate.relations {
+        let (Some(from), Some(to)) = (ids.get(&relation.from), ids.get(&relation.to)) else {
+            continue;
+        };
+        let arrow = match relation.kind {
+            RelationKind::Implements => "..|>",
+            RelationKind::Uses => "-->",
+        };
+        writeln!(out, "    {from} {arrow} {to}")?;
+    }
+
+    for (path, summary) in type_summaries(krate) {
+        writeln!(
+            out,
+            "    note for {} \"{}\"",
+            ids[&path],
+            summary.replace('"', "#quot;")
+        )?;
+    }
+
+    Ok(out)
+}
+
+fn write_class(
+    out: &mut String,
+    id: &str,
+    generics: Option<&str>,
+    members: &[String],
+) -> anyhow::Result<()> {
+    let generics = generic_names(generics);
+    let generics = if generics.is_empty() {
+        String::new()
+    } else {
+        format!("~{}~", generics.join(","))
+    };
+
+    // Mermaid does not accept an empty class body
+    if members.is_empty() {
+        writeln!(out, "        class {id}{generics}")?;
+        return Ok(());
+    }
+
+    writeln!(out, "        class {id}{generics} {{")?;
+    for member in members {
+        writeln!(out, "            {member}")?;
+    }
+    writeln!(out, "        }}")?;
+    Ok(())
+}
+
+/// Methods as `+name(params)$ Return`, where `$` marks associated functions
+fn method_lines(methods: Option<&Vec<&Function>>) -> impl Iterator<Item = String> {
+    methods.into_iter().flatten().map(|f| {
+        format!(
+            "{}{}({}){}{}",
+            uml_visibility(&f.visibility),
+            f.name,
+            f.params.iter().map(|p| escape(p)).join(", "),
+            if f.takes_self() { "" } else { "$" },
+            f.return_type
+                .as_ref()
+                .map(|ty| format!(" {}", escape(ty)))
+                .unwrap_or_default()
+        )
+    })
+}
+
+/// Mark a method line from [`method_lines`] as abstract with `*` after its parameters
+fn abstract_method(line: &str) -> String {
+    match line.split_once(')') {
+        Some((signature, rest)) => format!("{signature})*{rest}"),
+        None => line.to_owned(),
+    }
+}
+
+/// Escape Rust syntax that Mermaid would interpret inside a class body.
+///
+/// Generics use Mermaid's `~T~` notation, brackets are written as entity codes so a
+/// field like `f: fn(u8)` isn't taken for a method and braces don't close the class.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut previous = None;
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push('~'),
+            '>' if previous != Some('-') => escaped.push('~'),
+            '(' => escaped.push_str("#40;"),
+            ')' => escaped.push_str("#41;"),
+            '{' => escaped.push_str("#123;"),
+            '}' => escaped.push_str("#125;"),
+            _ => escaped.push(c),
+        }
+        previous = Some(c);
+    }
+    escaped
+}
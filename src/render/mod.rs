@@ -0,0 +1,107 @@
+//! Output formats rendered from the [`crate::model`]
+
+use std::collections::HashMap;
+
+use crate::model::{Crate, Function, Visibility, summary};
+
+pub mod d2;
+pub mod dot;
+pub mod drawio;
+pub mod excalidraw;
+pub mod graphml;
+pub mod html;
+pub mod json;
+pub mod layout;
+pub mod markdown;
+pub mod mermaid;
+pub mod plantuml;
+pub mod structurizr;
+pub mod svg;
+pub mod tikz;
+
+/// The methods of all impl blocks of the crate by the full path of their type
+pub fn methods_by_type(krate: &Crate) -> HashMap<&str, Vec<&Function>> {
+    let mut methods: HashMap<&str, Vec<&Function>> = HashMap::new();
+    for imp in krate.root.walk().flat_map(|m| &m.impls) {
+        if let Some(path) = &imp.self_path {
+            methods
+                .entry(path.as_str())
+                .or_default()
+                .extend(&imp.functions);
+        }
+    }
+    methods
+}
+
+/// The first paragraph of the doc comments of the documented types by their full path
+pub fn type_summaries(krate: &Crate) -> Vec<(String, String)> {
+    krate
+        .root
+        .walk()
+        .flat_map(|module| {
+            module
+                .structs
+                .iter()
+                .map(|s| (&s.name, &s.doc))
+                .chain(module.enums.iter().map(|e| (&e.name, &e.doc)))
+                .chain(module.traits.iter().map(|t| (&t.name, &t.doc)))
+                .filter_map(|(name, doc)| {
+                    summary(doc.as_deref()).map(|summary| (module.item_path(name), summary))
+                })
+        })
+        .collect()
+}
+
+/// Identifiers for the types of the crate by their full path.
+///
+/// A type is identified by its name, qualified with its module path joined by
+/// `separator` if another type of the crate has the same name.
+pub fn type_ids(krate: &Crate, separator: &str) -> HashMap<String, String> {
+    let mut by_name: HashMap<&str, Vec<(&str, String)>> = HashMap::new();
+    for module in krate.root.walk() {
+        for name in module.type_names() {
+            by_name
+                .entry(name)
+                .or_default()
+                .push((&module.path, module.item_path(name)));
+        }
+    }
+
+    by_name
+        .into_iter()
+        .flat_map(|(name, paths)| {
+            let unique = paths.len() == 1;
+            paths.into_iter().map(move |(module_path, path)| {
+                let id = if unique {
+                    name.to_owned()
+                } else {
+                    format!("{}{separator}{name}", module_path.replace("::", separator))
+                };
+                (path, id)
+            })
+        })
+        .collect()
+}
+
+/// The names of the type and const parameters in `<'a, T: Clone, const N: usize>`
+pub fn generic_names(generics: Option<&str>) -> Vec<String> {
+    generics
+        .and_then(|generics| syn::parse_str::<syn::Generics>(generics).ok())
+        .map(|generics| {
+            generics
+                .type_params()
+                .map(|param| param.ident.to_string())
+                .chain(generics.const_params().map(|param| param.ident.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The UML visibility marker: `+` public, `~` restricted to a path, `-` private
+pub const fn uml_visibility(visibility: &Visibility) -> char {
+    match visibility {
+        Visibility::Public => '+',
+        Visibility::Crate | Visibility::Super | Visibility::InPath(_) => '~',
+        Visibility::Private => '-',
+    }
+}
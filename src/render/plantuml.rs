@@ -0,0 +1,160 @@
+//! `PlantUML` class diagram output.
+
+use std::fmt::Write as _;
+
+use super::{methods_by_type, type_summaries, uml_visibility};
+use crate::model::{Crate, Function, Module, RelationKind};
+
+/// Render the crate as a `PlantUML` class diagram with one package per module
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let methods = methods_by_type(krate);
+    let mut out = String::from("@startuml\nset separator ::\nhide empty members\n\n");
+
+    write_package(&mut out, &krate.root, &krate.name, &methods, 0)?;
+    out.push('\n');
+
+    for relation in &krate.relations {
+        let arrow = match relation.kind {
+            RelationKind::Implements => "..|>",
+            RelationKind::Uses => "-->",
+        };
+        writeln!(out, "{} {arrow} {}", relation.from, relation.to)?;
+    }
+
+    for (path, summary) in type_summaries(krate) {
+        writeln!(out, "note top of {path} : {summary}")?;
+    }
+
+    out.push_str("@enduml\n");
+    Ok(out)
+}
+
+fn write_package(
+    out: &mut String,
+    module: &Module,
+    crate_name: &str,
+    methods: &std::collections::HashMap<&str, Vec<&Function>>,
+    depth: usize,
+) -> anyhow::Result<()> {
+    if !module.has_types() {
+        return Ok(());
+    }
+
+    let indent = "  ".repeat(depth);
+    if depth == 0 {
+        writeln!(out, "package \"{crate_name}\" as {} {{", module.name)?;
+    } else {
+        writeln!(out, "{indent}package {} {{", module.name)?;
+    }
+
+    for s in &module.structs {
+        writeln!(
+            out,
+            "{indent}  class {}{} {{",
+            s.name,
+            s.generics.as_deref().unwrap_or_default()
+        )?;
+        for (i, field) in s.fields.iter().enumerate() {
+            writeln!(
+                out,
+                "{indent}    {}{}{} : {}",
+                field_modifier(&field.ty),
+                uml_visibility(&field.visibility),
+                field.display_name(i),
+                field.ty
+            )?;
+        }
+        write_methods(
+            out,
+            &indent,
+            methods.get(module.item_path(&s.name).as_str()),
+        )?;
+        writeln!(out, "{indent}  }}")?;
+    }
+
+    for e in &module.enums {
+        writeln!(
+            out,
+            "{indent}  enum {}{} {{",
+            e.name,
+            e.generics.as_deref().unwrap_or_default()
+        )?;
+        for variant in &e.variants {
+            let variant = variant.to_string();
+            writeln!(out, "{indent}    {}{variant}", field_modifier(&variant))?;
+        }
+        write_methods(
+            out,
+            &indent,
+            methods.get(module.item_path(&e.name).as_str()),
+        )?;
+        writeln!(out, "{indent}  }}")?;
+    }
+
+    for t in &module.traits {
+        writeln!(
+            out,
+            "{indent}  interface {}{} {{",
+            t.name,
+            t.generics.as_deref().unwrap_or_default()
+        )?;
+        for member in t
+            .types
+            .iter()
+            .map(ToString::to_string)
+            .chain(t.consts.iter().map(ToString::to_string))
+        {
+            writeln!(out, "{indent}    {}{member}", field_modifier(&member))?;
+        }
+        for f in &t.required_methods {
+            writeln!(out, "{indent}    {{abstract}} {}", method(f))?;
+        }
+        for f in &t.provided_methods {
+            writeln!(out, "{indent}    {}", method(f))?;
+        }
+        write_methods(
+            out,
+            &indent,
+            methods.get(module.item_path(&t.name).as_str()),
+        )?;
+        writeln!(out, "{indent}  }}")?;
+    }
+
+    for submodule in &module.modules {
+        write_package(out, submodule, crate_name, methods, depth + 1)?;
+    }
+
+    writeln!(out, "{indent}}}")?;
+    Ok(())
+}
+
+fn write_methods(
+    out: &mut String,
+    indent: &str,
+    methods: Option<&Vec<&Function>>,
+) -> anyhow::Result<()> {
+    for f in methods.into_iter().flatten() {
+        writeln!(out, "{indent}    {}", method(f))?;
+    }
+    Ok(())
+}
+
+/// A method as `{static} +name(params) : Return`
+fn method(f: &Function) -> String {
+    format!(
+        "{}{}{}({}){}",
+        if f.takes_self() { "" } else { "{static} " },
+        uml_visibility(&f.visibility),
+        f.name,
+        f.params.join(", "),
+        f.return_type
+            .as_ref()
+            .map(|ty| format!(" : {ty}"))
+            .unwrap_or_default()
+    )
+}
+
+/// `PlantUML` takes every member containing parentheses for a method
+fn field_modifier(text: &str) -> &'static str {
+    if text.contains('(') { "{field} " } else { "" }
+}
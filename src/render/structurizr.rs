@@ -0,0 +1,109 @@
+//! Structurizr DSL output: a C4 component view of the crate.
+
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use crate::model::{Crate, RelationKind};
+
+/// Render the crate as a Structurizr workspace.
+///
+/// The crate is a container and its top-level modules are components. A component
+/// uses another one when one of its modules imports from the other, one of its types
+/// refers to a type of the other or implements one of its traits.
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let container = identifier(&krate.root.path);
+    let mut out = format!("workspace {} {{\n\n", quote(&krate.name));
+
+    out.push_str("    model {\n");
+    writeln!(
+        out,
+        "        system = softwareSystem {} {{",
+        quote(&krate.name)
+    )?;
+    writeln!(
+        out,
+        "            {container} = container {} {} \"Rust crate\" {{",
+        quote(&krate.name),
+        quote(&krate.root.summary().unwrap_or_default())
+    )?;
+    for module in &krate.root.modules {
+        writeln!(
+            out,
+            "                {} = component {} {} \"Rust module\"",
+            identifier(&module.path),
+            quote(&module.name),
+            quote(&module.summary().unwrap_or_default())
+        )?;
+    }
+    out.push_str("            }\n        }\n\n");
+
+    for ((from, to), reasons) in dependencies(krate) {
+        writeln!(
+            out,
+            "        {} -> {} {}",
+            identifier(from),
+            identifier(to),
+            quote(&reasons.join(", "))
+        )?;
+    }
+
+    out.push_str("    }\n\n    views {\n");
+    writeln!(
+        out,
+        "        component {container} \"Components\" {{\n            include *\n            autoLayout\n        }}"
+    )?;
+    out.push_str("    }\n}\n");
+    Ok(out)
+}
+
+/// The reasons each top-level module depends on another, by their paths
+fn dependencies(krate: &Crate) -> BTreeMap<(&str, &str), Vec<&'static str>> {
+    let components = &krate.root.modules;
+    let component_of = |path: &str| {
+        components
+            .iter()
+            .find(|m| path == m.path || path.starts_with(&format!("{}::", m.path)))
+            .map(|m| m.path.as_str())
+    };
+
+    let imports = components.iter().flat_map(|component| {
+        component.walk().flat_map(|m| &m.imports).map(|import| {
+            (
+                Some(component.path.as_str()),
+                component_of(import),
+                "Imports from",
+            )
+        })
+    });
+    let type_references = krate.relations.iter().map(|relation| {
+        (
+            component_of(&relation.from),
+            component_of(&relation.to),
+            match relation.kind {
+                RelationKind::Uses => "Uses types of",
+                RelationKind::Implements => "Implements traits of",
+            },
+        )
+    });
+
+    let mut dependencies: BTreeMap<(&str, &str), Vec<&'static str>> = BTreeMap::new();
+    for (from, to, reason) in imports.chain(type_references) {
+        if let (Some(from), Some(to)) = (from, to)
+            && from != to
+        {
+            let reasons = dependencies.entry((from, to)).or_default();
+            if !reasons.contains(&reason) {
+                reasons.push(reason);
+            }
+        }
+    }
+    dependencies
+}
+
+/// A DSL identifier for a module, e.g. `crate_render` for `crate::render`
+fn identifier(path: &str) -> String {
+    path.replace("::", "_")
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,179 @@
+//! Standalone SVG output drawn with the built-in [`layout`](super::layout).
+
+use itertools::Itertools as _;
+use std::fmt::Write as _;
+
+use super::layout::{self, Card, CardKind, Edge, FRAME_HEADER, Frame, LINE_HEIGHT, PADDING, Rect};
+use crate::model::{Crate, RelationKind};
+
+const STYLE: &str = r"
+    text { font-family: monospace; font-size: 12px; fill: #000; }
+    .frame { fill: #fafafa; stroke: #d0d0d0; }
+    .frame-header { fill: #e9ecef; }
+    .frame-name { font-size: 14px; font-weight: bold; }
+    .card { stroke: #e0e0e0; }
+    .card-title { font-weight: bold; }
+    .compartment { stroke: #e0e0e0; }
+    .struct { fill: #ffffff; }
+    .struct-header { fill: #f8f9fa; }
+    .enum { fill: #f0f9f0; }
+    .enum-header { fill: #e8f5e8; }
+    .trait { fill: #f5f0ff; }
+    .trait-header { fill: #ece4fb; }
+    .function { fill: #f0f4ff; }
+    .function-header { fill: #f0f4ff; }
+    .impl { fill: #fff4e6; }
+    .impl-header { fill: #f4ede6; }
+    .edge { fill: none; stroke: #555; }
+    .implements { stroke-dasharray: 6 4; }
+";
+
+/// Render the crate as a standalone SVG image
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    let layout = layout::layout(krate);
+    let margin = 2.0 * PADDING;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="{x} {x} {w} {h}">"#,
+        w = 2.0f64.mul_add(margin, layout.width),
+        h = 2.0f64.mul_add(margin, layout.height),
+        x = -margin,
+    )?;
+    writeln!(out, "<title>{}</title>", escape(&krate.name))?;
+    writeln!(out, "<style>{STYLE}</style>")?;
+    out.push_str(concat!(
+        "<defs>\n",
+        r##"    <marker id="uses" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10" fill="none" stroke="#555"/></marker>"##,
+        "\n",
+        r##"    <marker id="implements" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="10" markerHeight="10" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="#fff" stroke="#555"/></marker>"##,
+        "\n</defs>\n"
+    ));
+
+    for frame in &layout.frames {
+        write_frame(&mut out, frame)?;
+    }
+    for edge in &layout.edges {
+        write_edge(&mut out, edge)?;
+    }
+    for card in &layout.cards {
+        write_card(&mut out, card)?;
+    }
+
+    out.push_str("</svg>\n");
+    Ok(out)
+}
+
+/// A module box with its name in the header
+fn write_frame(out: &mut String, frame: &Frame) -> anyhow::Result<()> {
+    let Rect {
+        x,
+        y,
+        width,
+        height,
+    } = frame.rect;
+    writeln!(
+        out,
+        r#"<g class="module" data-path="{path}"><rect class="frame" x="{x}" y="{y}" width="{width}" height="{height}" rx="8"/><path class="frame-header" d="M {x} {top} a 8 8 0 0 1 8 -8 h {inner} a 8 8 0 0 1 8 8 v {header} h -{width} z"/><text class="frame-name" x="{cx}" y="{ty}" text-anchor="middle">{name}</text></g>"#,
+        top = y + 8.0,
+        inner = width - 16.0,
+        header = FRAME_HEADER - 8.0,
+        cx = frame.rect.center_x(),
+        ty = y + FRAME_HEADER - 9.0,
+        name = escape(&frame.name),
+        path = escape(&frame.path),
+    )?;
+    Ok(())
+}
+
+fn write_edge(out: &mut String, edge: &Edge) -> anyhow::Result<()> {
+    let (class, marker) = match edge.kind {
+        RelationKind::Implements => ("edge implements", "implements"),
+        RelationKind::Uses => ("edge", "uses"),
+    };
+    let points = edge
+        .points
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .join(" ");
+    writeln!(
+        out,
+        r#"<polyline class="{class}" points="{points}" marker-end="url(#{marker})"/>"#
+    )?;
+    Ok(())
+}
+
+/// A card styled like its HTML counterpart: a title followed by compartments of lines
+fn write_card(out: &mut String, card: &Card) -> anyhow::Result<()> {
+    let class = match card.kind {
+        CardKind::Struct => "struct",
+        CardKind::Enum => "enum",
+        CardKind::Trait => "trait",
+        CardKind::Function => "function",
+        CardKind::Impl => "impl",
+    };
+    let Rect {
+        x,
+        y,
+        width,
+        height,
+    } = card.rect;
+    let header = 2.0f64.mul_add(PADDING, LINE_HEIGHT);
+
+    writeln!(out, r#"<g class="{class}-card">"#)?;
+    writeln!(
+        out,
+        r#"    <rect class="card {class}" x="{x}" y="{y}" width="{width}" height="{height}" rx="6"/>"#
+    )?;
+    if !card.compartments.is_empty() {
+        writeln!(
+            out,
+            r#"    <rect class="{class}-header" x="{x1}" y="{y1}" width="{w}" height="{h}" rx="5"/>"#,
+            x1 = x + 0.5,
+            y1 = y + 0.5,
+            w = width - 1.0,
+            h = header - 1.0,
+        )?;
+    }
+    writeln!(
+        out,
+        r#"    <text class="card-title" x="{cx}" y="{ty}" text-anchor="middle">{title}</text>"#,
+        cx = card.rect.center_x(),
+        ty = y + PADDING + LINE_HEIGHT - 4.0,
+        title = escape(&card.title),
+    )?;
+
+    let mut top = y + header;
+    for lines in &card.compartments {
+        writeln!(
+            out,
+            r#"    <line class="compartment" x1="{x}" y1="{top}" x2="{x2}" y2="{top}"/>"#,
+            x2 = x + width,
+        )?;
+        for (i, line) in lines.iter().enumerate() {
+            #[expect(clippy::cast_precision_loss, reason = "line counts are small")]
+            let baseline = (i as f64 + 1.0).mul_add(LINE_HEIGHT, top + PADDING) - 4.0;
+            writeln!(
+                out,
+                r#"    <text x="{tx}" y="{baseline}" xml:space="preserve">{line}</text>"#,
+                tx = x + PADDING,
+                line = escape(line),
+            )?;
+        }
+        #[expect(clippy::cast_precision_loss, reason = "line counts are small")]
+        {
+            top += (lines.len() as f64).mul_add(LINE_HEIGHT, 2.0 * PADDING);
+        }
+    }
+    writeln!(out, "</g>")?;
+    Ok(())
+}
+
+/// Escape text for XML content and attributes
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,173 @@
+//! LaTeX output: a `TikZ` picture drawn with the built-in [`layout`](super::layout).
+
+use itertools::Itertools as _;
+use std::fmt::Write as _;
+
+use super::layout::{self, Card, CardKind, Edge, FRAME_HEADER, Frame, LINE_HEIGHT, PADDING};
+use crate::model::{Crate, RelationKind};
+
+/// Scale from layout units to points, chosen so `\footnotesize\ttfamily` fits the cards
+const SCALE: f64 = 0.6;
+
+/// Colors mirroring the SVG output
+const COLORS: [(&str, &str); 9] = [
+    ("diagenframe", "FAFAFA"),
+    ("diagenframeheader", "E9ECEF"),
+    ("diagenborder", "D0D0D0"),
+    ("diagenstruct", "FFFFFF"),
+    ("diagenenum", "F0F9F0"),
+    ("diagentrait", "F5F0FF"),
+    ("diagenfunction", "F0F4FF"),
+    ("diagenimpl", "FFF4E6"),
+    ("diagenedge", "555555"),
+];
+
+/// Render the crate as a standalone LaTeX document
+pub fn render(krate: &Crate) -> anyhow::Result<String> {
+    Ok(format!(
+        "\\documentclass[tikz, border=4pt]{{standalone}}\n\\begin{{document}}\n{}\\end{{document}}\n",
+        render_fragment(krate)?
+    ))
+}
+
+/// Render the crate as a `tikzpicture` to `\input` into a document that loads `TikZ`
+pub fn render_fragment(krate: &Crate) -> anyhow::Result<String> {
+    let layout = layout::layout(krate);
+
+    let mut out = format!(
+        "% {} generated by diagen {}\n",
+        escape(&krate.name),
+        env!("CARGO_PKG_VERSION")
+    );
+    writeln!(
+        out,
+        "\\begin{{tikzpicture}}[x={SCALE}pt, y=-{SCALE}pt, font=\\footnotesize\\ttfamily, line width=0.4pt,"
+    )?;
+    out.push_str(concat!(
+        "    every node/.style={inner sep=0pt, outer sep=0pt},\n",
+        "    uses/.style={draw=diagenedge, -stealth},\n",
+        "    implements/.style={draw=diagenedge, dashed, -latex}]\n",
+    ));
+    for (name, hex) in COLORS {
+        writeln!(out, "\\definecolor{{{name}}}{{HTML}}{{{hex}}}")?;
+    }
+
+    for frame in &layout.frames {
+        write_frame(&mut out, frame)?;
+    }
+    for edge in &layout.edges {
+        write_edge(&mut out, edge)?;
+    }
+    for card in &layout.cards {
+        write_card(&mut out, card)?;
+    }
+
+    out.push_str("\\end{tikzpicture}\n");
+    Ok(out)
+}
+
+fn write_frame(out: &mut String, frame: &Frame) -> anyhow::Result<()> {
+    let rect = frame.rect;
+    writeln!(
+        out,
+        "\\draw[draw=diagenborder, fill=diagenframe, rounded corners=4pt] ({}, {}) rectangle ++({}, {});",
+        rect.x, rect.y, rect.width, rect.height
+    )?;
+    writeln!(
+        out,
+        "\\fill[diagenframeheader] ({x}, {y}) rectangle ++({w}, {h});\n\\node[font=\\small\\ttfamily\\bfseries] at ({cx}, {cy}) {{{name}}};",
+        x = rect.x + 0.5,
+        y = rect.y + 0.5,
+        w = rect.width - 1.0,
+        h = FRAME_HEADER - 1.0,
+        cx = rect.center_x(),
+        cy = rect.y + FRAME_HEADER / 2.0,
+        name = escape(&frame.name),
+    )?;
+    Ok(())
+}
+
+fn write_edge(out: &mut String, edge: &Edge) -> anyhow::Result<()> {
+    let style = match edge.kind {
+        RelationKind::Implements => "implements",
+        RelationKind::Uses => "uses",
+    };
+    writeln!(
+        out,
+        "\\draw[{style}] {};",
+        edge.points
+            .iter()
+            .map(|(x, y)| format!("({x}, {y})"))
+            .join(" -- ")
+    )?;
+    Ok(())
+}
+
+/// A card: the title followed by compartments of left-aligned lines
+fn write_card(out: &mut String, card: &Card) -> anyhow::Result<()> {
+    let fill = match card.kind {
+        CardKind::Struct => "diagenstruct",
+        CardKind::Enum => "diagenenum",
+        CardKind::Trait => "diagentrait",
+        CardKind::Function => "diagenfunction",
+        CardKind::Impl => "diagenimpl",
+    };
+    let rect = card.rect;
+    let header = 2.0f64.mul_add(PADDING, LINE_HEIGHT);
+
+    writeln!(
+        out,
+        "\\draw[draw=diagenborder, fill={fill}] ({}, {}) rectangle ++({}, {});",
+        rect.x, rect.y, rect.width, rect.height
+    )?;
+    writeln!(
+        out,
+        "\\node[font=\\footnotesize\\ttfamily\\bfseries] at ({}, {}) {{{}}};",
+        rect.center_x(),
+        rect.y + header / 2.0,
+        escape(&card.title)
+    )?;
+
+    let mut top = rect.y + header;
+    for lines in &card.compartments {
+        writeln!(
+            out,
+            "\\draw[diagenborder] ({}, {top}) -- ++({}, 0);",
+            rect.x, rect.width
+        )?;
+        writeln!(
+            out,
+            "\\node[anchor=north west, align=left, text depth=0pt] at ({}, {}) {{{}}};",
+            rect.x + PADDING,
+            top + PADDING,
+            lines.iter().map(|line| escape(line)).join("\\\\\n    ")
+        )?;
+        #[expect(clippy::cast_precision_loss, reason = "line counts are small")]
+        {
+            top += (lines.len() as f64).mul_add(LINE_HEIGHT, 2.0 * PADDING);
+        }
+    }
+    Ok(())
+}
+
+/// Escape text for LaTeX in a typewriter font, which has `<`, `>` and `"` but would
+/// turn the `'` of lifetimes into a curly quote
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '$' | '&' | '%' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\'' => escaped.push_str("\\textquotesingle{}"),
+            '•' => escaped.push_str("\\textbullet{}"),
+            '…' => escaped.push_str("\\dots{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,66 @@
+use crate::{Format, Generator, find_path};
+
+use std::path::PathBuf;
+
+#[test]
+fn test_find_path_with_file() {
+    let file_path = PathBuf::from("src/main.rs");
+    let result = find_path(Some(file_path.clone()));
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), file_path);
+}
+
+#[test]
+fn test_find_path_with_directory() {
+    let dir_path = PathBuf::from("src");
+    let result = find_path(Some(dir_path));
+    assert!(result.is_ok());
+    let found_path = result.unwrap();
+    assert!(found_path.ends_with("main.rs") || found_path.ends_with("lib.rs"));
+}
+
+#[test]
+fn test_find_path_without_input() {
+    let result = find_path(None);
+    assert!(result.is_ok());
+    let found_path = result.unwrap();
+    assert!(found_path.ends_with("main.rs") || found_path.ends_with("lib.rs"));
+}
+
+#[test]
+fn test_generator_from_crate_root() {
+    let krate = Generator::from_path(".").parse().unwrap();
+    assert!(
+        krate.root.modules.iter().any(|m| m.name == "model"),
+        "lib.rs should be used as the entry point"
+    );
+}
+
+#[test]
+fn test_generator_from_source() {
+    let generator = Generator::from_source(
+        "pub struct Point { pub x: i32, y: i32 }\n#[cfg(test)]\nmod tests { fn helper() {} }",
+    )
+    .format(Format::Html)
+    .title("Points");
+
+    let krate = generator.parse().unwrap();
+    assert_eq!(krate.root.structs.len(), 1, "Point should be parsed");
+    assert!(krate.root.modules.is_empty(), "tests should be excluded");
+
+    let html = generator.render(&krate).unwrap();
+    assert!(
+        html.contains("<title>Points</title>"),
+        "title should be set"
+    );
+    assert!(html.contains("Point"), "struct should be rendered");
+}
+
+#[test]
+fn test_generator_include_tests() {
+    let krate = Generator::from_source("#[test]\nfn it_works() {}")
+        .include_tests(true)
+        .parse()
+        .unwrap();
+    assert_eq!(krate.root.functions.len(), 1, "test should be included");
+}
@@ -1,4 +1,8 @@
-use crate::logic::{organize_and_render_items, parse_file_recursive};
+use crate::logic::{
+    parse_crate, parse_file_recursive, parse_source, retain_attributes, retain_visible,
+};
+use crate::model::{Impl, Module, Relation, RelationKind, Visibility, VisibilityLevel, summary};
+use crate::render::html::organize_and_render_items;
 use std::path::PathBuf;
 
 #[test]
@@ -17,9 +21,755 @@ fn test_parse_file_recursive_invalid_path() {
 
 #[test]
 fn test_organize_and_render_items_empty() {
-    let path = PathBuf::from("src/main.rs");
-    let items = vec![];
-    let result = organize_and_render_items(&path, items, false, false);
+    let module = Module::default();
+    let result = organize_and_render_items(&module, None);
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "");
 }
+
+#[test]
+fn test_parse_crate_builds_module_tree() {
+    let krate = parse_crate("src/lib.rs", false).unwrap();
+    assert_eq!(krate.root.path, "crate");
+
+    let items = krate
+        .root
+        .modules
+        .iter()
+        .find(|m| m.name == "items")
+        .expect("items module should be parsed");
+    assert_eq!(items.path, "crate::items");
+    assert!(
+        items.modules.iter().any(|m| m.name == "structs"),
+        "file modules should be nested"
+    );
+    assert!(
+        krate.root.modules.iter().all(|m| m.name != "tests"),
+        "test modules should be excluded"
+    );
+}
+
+#[test]
+fn test_parse_crate_resolves_field_relations() {
+    let krate = parse_crate("src/lib.rs", false).unwrap();
+    assert!(
+        krate.relations.iter().any(|r| r.kind == RelationKind::Uses
+            && r.from == "crate::model::Module"
+            && r.to == "crate::model::Struct"),
+        "Module::structs should relate Module to Struct"
+    );
+}
+
+#[test]
+fn test_parse_source_module_docs_and_imports() {
+    let krate = parse_source(
+        r"
+//! The crate.
+//!
+//! More details.
+
+use std::fmt;
+use shapes::{Shape, kinds::*};
+
+/// Geometric shapes
+mod shapes {
+    //! Second paragraph.
+    use super::Config;
+    use self::kinds::Kind as K;
+    pub struct Shape;
+    pub mod kinds {
+        use crate::{Config, shapes::{self, Shape}};
+        pub enum Kind {}
+    }
+}
+pub struct Config;
+",
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        krate.root.doc.as_deref(),
+        Some("The crate.\n\nMore details.")
+    );
+    assert_eq!(krate.root.summary().as_deref(), Some("The crate."));
+    assert_eq!(
+        krate.root.imports,
+        ["crate::shapes::Shape", "crate::shapes::kinds::*"]
+    );
+
+    let shapes = &krate.root.modules[0];
+    assert_eq!(
+        shapes.doc.as_deref(),
+        Some("Geometric shapes\n\nSecond paragraph.")
+    );
+    assert_eq!(
+        shapes.imports,
+        ["crate::Config", "crate::shapes::kinds::Kind"]
+    );
+    assert_eq!(
+        shapes.modules[0].imports,
+        ["crate::Config", "crate::shapes", "crate::shapes::Shape"]
+    );
+}
+
+#[test]
+fn test_parse_source_traits() {
+    let krate = parse_source(
+        r"
+pub trait Shape<T>: Clone + std::fmt::Debug {
+    type Unit: Copy + Default = f64;
+    const SIDES: usize;
+    fn area(&self) -> Self::Unit;
+    fn describe(&self) -> String {
+        String::new()
+    }
+    #[test]
+    fn sides_are_positive() {}
+}
+#[derive(Clone, Debug)]
+pub struct Square;
+impl Shape<u8> for Square {
+    type Unit = f64;
+    const SIDES: usize = 4;
+    fn area(&self) -> f64 { 1.0 }
+}
+",
+        false,
+    )
+    .unwrap();
+
+    let shape = &krate.root.traits[0];
+    assert_eq!(shape.generics.as_deref(), Some("<T>"));
+    assert_eq!(
+        shape.supertraits(),
+        Some(": Clone + std::fmt::Debug".to_owned())
+    );
+    assert_eq!(
+        shape.types[0].to_string(),
+        "type Unit: Copy + Default = f64"
+    );
+    assert_eq!(shape.consts[0].to_string(), "const SIDES: usize");
+    assert_eq!(shape.required_methods[0].name, "area");
+    assert_eq!(
+        shape.provided_methods.len(),
+        1,
+        "test methods are excluded: {:?}",
+        shape.provided_methods
+    );
+    assert_eq!(shape.provided_methods[0].name, "describe");
+
+    assert!(
+        krate
+            .relations
+            .iter()
+            .any(|r| r.kind == RelationKind::Implements
+                && r.from == "crate::Square"
+                && r.to == "crate::Shape"),
+        "{:?}",
+        krate.relations
+    );
+}
+
+#[test]
+fn test_parse_source_type_aliases_consts_and_statics() {
+    let source = r#"
+pub type Result<T> = std::result::Result<T, Error>;
+pub(crate) const GREETING: &str = "a greeting that is much too long to show in full";
+static mut COUNTER: u32 = 0;
+pub static NAMES: [&str; 2] = ["a", "b"];
+#[cfg(test)]
+const FIXTURE: u8 = 1;
+"#;
+    let root = parse_source(source, false).unwrap().root;
+
+    assert_eq!(
+        root.type_aliases[0].to_string(),
+        "type Result<T> = std::result::Result<T, Error>"
+    );
+    assert_eq!(root.consts.len(), 1, "test consts are excluded");
+    assert_eq!(
+        root.consts[0].value,
+        "\"a greeting that is much too long to show in full\""
+    );
+    assert_eq!(
+        root.consts[0].to_string(),
+        "const GREETING: &str = \"a greeting that is much too long to sh…"
+    );
+    assert!(root.statics[0].is_mut);
+    assert_eq!(root.statics[0].to_string(), "static mut COUNTER: u32 = 0");
+    assert!(!root.statics[1].is_mut);
+
+    let root = parse_source(source, true).unwrap().root;
+    assert_eq!(root.consts.len(), 2);
+}
+
+#[test]
+fn test_parse_source_unions_and_extern_blocks() {
+    let source = r#"
+#[repr(C)]
+pub union Value {
+    pub int: i64,
+    float: f64,
+}
+unsafe extern "system" {
+    pub type Handle;
+    pub static mut LAST_ERROR: u32;
+    pub fn open(path: *const u8) -> *mut Handle;
+    #[cfg(test)]
+    fn mock();
+}
+pub extern "C" fn callback(code: i32) {}
+"#;
+    let root = parse_source(source, false).unwrap().root;
+
+    let value = &root.unions[0];
+    assert_eq!(value.name, "Value");
+    assert_eq!(value.fields[1].name.as_deref(), Some("float"));
+    assert_eq!(value.fields[1].visibility, Visibility::Private);
+
+    let block = &root.extern_blocks[0];
+    assert_eq!(block.abi, "system");
+    assert_eq!(block.types, ["Handle"]);
+    assert_eq!(block.statics[0].to_string(), "static mut LAST_ERROR: u32");
+    assert_eq!(block.functions.len(), 1, "test functions are excluded");
+    assert_eq!(
+        block.functions[0].signature(),
+        "pub fn open(path: *const u8) -> *mut Handle"
+    );
+
+    assert_eq!(
+        root.functions[0].signature(),
+        r#"pub extern "C" fn callback(code: i32)"#
+    );
+}
+
+#[test]
+fn test_parse_source_macros() {
+    let source = r"
+/// Build a map.
+///
+/// Keys and values are cloned.
+#[macro_export]
+macro_rules! map {
+    () => { HashMap::new() };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map = HashMap::new();
+        $(map.insert($key, $value);)+
+        map
+    }};
+}
+macro_rules! internal {
+    [$x:ident] => ($x)
+}
+#[cfg(test)]
+macro_rules! fixture {
+    () => {};
+}
+";
+    let root = parse_source(source, false).unwrap().root;
+
+    assert_eq!(root.macros.len(), 2, "test macros are excluded");
+    let map = &root.macros[0];
+    assert_eq!(map.name, "map");
+    assert!(map.exported);
+    assert_eq!(summary(map.doc.as_deref()).as_deref(), Some("Build a map."));
+    assert_eq!(map.arms, ["()", "($($key:expr => $value:expr),+ $(,)?)"]);
+
+    let internal = &root.macros[1];
+    assert!(!internal.exported);
+    assert_eq!(internal.arms, ["[$x:ident]"]);
+}
+
+#[test]
+fn test_parse_source_excludes_test_impls() {
+    let source = r"
+pub struct Vis;
+impl Vis {
+    pub fn show(&self) {}
+}
+#[cfg(test)]
+impl Vis {
+    fn test_helper() {}
+}
+";
+    let root = parse_source(source, false).unwrap().root;
+    assert_eq!(root.impls.len(), 1, "test impls are excluded");
+    assert_eq!(root.impls[0].functions[0].name, "show");
+
+    let root = parse_source(source, true).unwrap().root;
+    assert_eq!(root.impls.len(), 2);
+    assert_eq!(root.impls[1].functions[0].name, "test_helper");
+}
+
+#[test]
+fn test_parse_source_impl_associated_items() {
+    let source = r"
+pub struct Counter;
+impl Iterator for Counter {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+impl Counter {
+    pub const START: u32 = 1;
+    #[cfg(test)]
+    const FIXTURE: u32 = 0;
+}
+";
+    let root = parse_source(source, false).unwrap().root;
+
+    assert_eq!(
+        root.impls[0].associated_items().collect::<Vec<_>>(),
+        ["type Item = u32"]
+    );
+    assert_eq!(
+        root.impls[1].associated_items().collect::<Vec<_>>(),
+        ["const START: u32 = 1"],
+        "test consts are excluded"
+    );
+    assert!(!root.impls[1].is_empty());
+}
+
+#[test]
+fn test_parse_source_visibility() {
+    let source = r"
+pub mod outer {
+    pub struct Fields {
+        pub a: u8,
+        pub(crate) b: u8,
+        pub(super) c: u8,
+        pub(in crate::outer) d: u8,
+        pub(self) e: u8,
+        f: u8,
+        pub(in crate) g: u8,
+    }
+}
+";
+    let root = parse_source(source, false).unwrap().root;
+    let fields = &root.modules[0].structs[0].fields;
+
+    assert_eq!(
+        fields.iter().map(|f| &f.visibility).collect::<Vec<_>>(),
+        [
+            &Visibility::Public,
+            &Visibility::Crate,
+            &Visibility::Super,
+            &Visibility::InPath("crate::outer".to_owned()),
+            &Visibility::Private,
+            &Visibility::Private,
+            &Visibility::Crate,
+        ]
+    );
+    assert_eq!(fields[3].visibility.to_string(), "pub(in crate::outer)");
+    assert_eq!(fields[3].visibility.level(), VisibilityLevel::Module);
+    assert_eq!(fields[5].visibility.keyword(), None);
+}
+
+#[test]
+fn test_retain_visible() {
+    let source = r"
+pub struct Api {
+    pub config: Config,
+    pub(crate) cache: Cache,
+}
+pub struct Config;
+pub(crate) struct Cache;
+impl Cache {
+    pub fn clear(&self) {}
+}
+impl Api {
+    pub fn new() -> Self { todo!() }
+    pub(crate) fn reset(&mut self) {}
+    fn check(&self) {}
+}
+impl Default for Config {
+    fn default() -> Self { Self }
+}
+pub(crate) fn helper() {}
+";
+    let mut krate = parse_source(source, false).unwrap();
+    retain_visible(&mut krate, VisibilityLevel::Crate);
+    assert_eq!(krate.root.structs.len(), 3);
+    assert_eq!(krate.root.impls[1].functions.len(), 2);
+    assert_eq!(krate.root.functions.len(), 1);
+
+    retain_visible(&mut krate, VisibilityLevel::Public);
+    let root = &krate.root;
+    assert_eq!(
+        root.structs
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Api", "Config"]
+    );
+    assert_eq!(root.structs[0].fields.len(), 1);
+    assert!(root.functions.is_empty());
+    assert_eq!(
+        root.impls.iter().map(Impl::header).collect::<Vec<_>>(),
+        ["impl Api", "impl Default for Config"],
+        "impls of dropped types are dropped"
+    );
+    assert_eq!(root.impls[0].functions[0].name, "new");
+    assert_eq!(root.impls[0].functions.len(), 1);
+    assert_eq!(
+        krate.relations,
+        [Relation {
+            kind: RelationKind::Uses,
+            from: "crate::Api".to_owned(),
+            to: "crate::Config".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn test_doc_comments() {
+    let source = r"
+//! The crate.
+
+/// A point.
+///
+/// Used everywhere.
+pub struct Point {
+    /// Horizontal position
+    pub x: i32,
+}
+pub enum Shape {
+    /// A circle
+    Circle,
+}
+impl Point {
+    /// The origin
+    pub fn origin() -> Self { todo!() }
+}
+";
+    let krate = parse_source(source, false).unwrap();
+    let root = &krate.root;
+    assert_eq!(root.doc.as_deref(), Some("The crate."));
+    assert_eq!(
+        root.structs[0].doc.as_deref(),
+        Some("A point.\n\nUsed everywhere.")
+    );
+    assert_eq!(
+        root.structs[0].fields[0].doc.as_deref(),
+        Some("Horizontal position")
+    );
+    assert_eq!(root.enums[0].doc, None);
+    assert_eq!(root.enums[0].variants[0].doc.as_deref(), Some("A circle"));
+    assert_eq!(
+        root.impls[0].functions[0].doc.as_deref(),
+        Some("The origin")
+    );
+}
+
+#[test]
+fn test_attributes() {
+    let source = r#"
+#[derive(Debug, Clone, serde::Serialize)]
+#[non_exhaustive]
+#[repr(C)]
+#[serde(rename_all = "camelCase")]
+pub struct Config;
+
+#[must_use]
+#[inline]
+pub fn build() -> Config { Config }
+"#;
+    let mut krate = parse_source(source, false).unwrap();
+    assert_eq!(
+        krate.root.structs[0].derives,
+        ["Debug", "Clone", "serde::Serialize"]
+    );
+    assert_eq!(
+        krate.root.structs[0].attributes,
+        [
+            "non_exhaustive",
+            "repr(C)",
+            r#"serde(rename_all = "camelCase")"#
+        ]
+    );
+
+    retain_attributes(&mut krate, &["repr".to_owned(), "must_use".to_owned()]);
+    assert!(krate.root.structs[0].derives.is_empty());
+    assert_eq!(krate.root.structs[0].attributes, ["repr(C)"]);
+    assert_eq!(krate.root.functions[0].attributes, ["must_use"]);
+}
+
+#[test]
+fn test_generic_signatures() {
+    let source = r"
+pub fn read_all<'a, R: Read + 'a, const N: usize>(reader: &'a mut R) -> [u8; N]
+where
+    R: Send,
+{
+    todo!()
+}
+pub struct Wrapper<T>(T);
+impl<T> Wrapper<T>
+where
+    T: Clone,
+{
+    pub fn map<U, F: Fn(T) -> U>(&self, f: F) -> Wrapper<U> { todo!() }
+}
+";
+    let krate = parse_source(source, false).unwrap();
+    let read_all = &krate.root.functions[0];
+    assert_eq!(
+        read_all.generics.as_deref(),
+        Some("<'a, R: Read + 'a, const N: usize>")
+    );
+    assert_eq!(read_all.where_clause, ["R: Send"]);
+    assert_eq!(
+        read_all.signature(),
+        "pub fn read_all<'a, R: Read + 'a, const N: usize>(reader: &'a mut R) -> [u8; N] where R: Send"
+    );
+
+    let imp = &krate.root.impls[0];
+    assert_eq!(imp.header(), "impl<T> Wrapper<T> where T: Clone");
+    assert_eq!(
+        imp.functions[0].signature(),
+        "pub fn map<U, F: Fn(T) -> U>(&self, f: F) -> Wrapper<U>"
+    );
+}
+
+#[test]
+fn test_function_modifier_order() {
+    let source = r#"
+pub const async unsafe extern "C" fn all() {}
+pub const unsafe fn raw(ptr: *const u8) -> u8 { *ptr }
+pub async unsafe fn fetch() {}
+"#;
+    let root = parse_source(source, false).unwrap().root;
+    assert_eq!(
+        root.functions[0].signature(),
+        r#"pub const async unsafe extern "C" fn all()"#
+    );
+    assert_eq!(
+        root.functions[1].signature(),
+        "pub const unsafe fn raw(ptr: *const u8) -> u8"
+    );
+    assert_eq!(root.functions[2].signature(), "pub async unsafe fn fetch()");
+}
+
+#[test]
+fn test_enum_discriminants() {
+    let source = r#"
+#[repr(u8)]
+#[derive(Default)]
+pub enum Opcode {
+    #[default]
+    Nop = 0,
+    #[serde(rename = "load")]
+    Load = 0x10,
+    Store = Self::BASE + 1,
+    Halt,
+}
+"#;
+    let krate = parse_source(source, false).unwrap();
+    let opcode = &krate.root.enums[0];
+    assert_eq!(opcode.attributes, ["repr(u8)"]);
+    assert_eq!(
+        opcode
+            .variants
+            .iter()
+            .map(|v| v.discriminant.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("0"), Some("0x10"), Some("Self::BASE + 1"), None]
+    );
+    assert_eq!(opcode.variants[0].attributes, ["default"]);
+    assert_eq!(opcode.variants[1].attributes, [r#"serde(rename = "load")"#]);
+    assert_eq!(opcode.variants[1].to_string(), "Load = 0x10");
+}
+
+#[test]
+fn test_variant_fields_have_enum_visibility() {
+    let source = r"
+pub enum Input {
+    Path(PathBuf),
+    Source { text: String },
+}
+pub(crate) enum State {
+    Running(u32),
+}
+";
+    let root = parse_source(source, false).unwrap().root;
+    let input = &root.enums[0];
+    assert_eq!(input.variants[0].fields[0].visibility, Visibility::Public);
+    assert_eq!(input.variants[1].fields[0].visibility, Visibility::Public);
+    assert_eq!(
+        root.enums[1].variants[0].fields[0].visibility,
+        Visibility::Crate
+    );
+}
+
+#[test]
+fn test_qualified_paths_resolve_to_their_module() {
+    let source = r"
+pub struct Other;
+pub struct Local { x: inner::Other, y: Other }
+pub mod inner {
+    pub struct Other;
+}
+pub mod a {
+    pub mod b {
+        pub struct Deep { x: crate::Other, y: super::super::inner::Other }
+    }
+}
+";
+    let krate = parse_source(source, false).unwrap();
+    let uses = |from: &str| {
+        krate
+            .relations
+            .iter()
+            .filter(|r| r.kind == RelationKind::Uses && r.from == from)
+            .map(|r| r.to.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        uses("crate::Local"),
+        ["crate::Other", "crate::inner::Other"]
+    );
+    assert_eq!(
+        uses("crate::a::b::Deep"),
+        ["crate::Other", "crate::inner::Other"]
+    );
+}
+
+#[test]
+fn test_same_name_in_two_modules_resolves_through_imports() {
+    let source = r"
+pub mod first {
+    pub struct Config;
+}
+pub mod second {
+    pub struct Config;
+}
+pub mod app {
+    use crate::second::Config;
+    pub struct App { config: Config }
+}
+pub mod tool {
+    use crate::first::*;
+    pub struct Tool { config: Config }
+}
+pub struct Ambiguous { config: Config }
+";
+    let krate = parse_source(source, false).unwrap();
+    let target = |from: &str| {
+        krate
+            .relations
+            .iter()
+            .find(|r| r.from == from)
+            .map(|r| r.to.as_str())
+    };
+    assert_eq!(target("crate::app::App"), Some("crate::second::Config"));
+    assert_eq!(target("crate::tool::Tool"), Some("crate::first::Config"));
+    assert_eq!(target("crate::Ambiguous"), None);
+}
+
+#[test]
+fn test_imports_of_other_crates_shadow_crate_types() {
+    let source = r"
+pub mod error {
+    pub enum Error { Io }
+    pub trait Display {}
+}
+mod reader {
+    use std::fmt::Display;
+    use std::io::{self, Error};
+    pub struct Reader { last: Option<Error>, other: io::Error }
+    impl Display for Reader {}
+}
+";
+    let krate = parse_source(source, false).unwrap();
+    assert!(krate.relations.is_empty(), "{:?}", krate.relations);
+    assert!(krate.root.modules[1].imports.is_empty());
+    assert_eq!(krate.root.modules[1].impls[0].trait_path, None);
+}
+
+#[test]
+fn test_renamed_imports_resolve_through_their_alias() {
+    let source = r"
+pub mod first {
+    pub struct Config;
+}
+pub mod second {
+    pub struct Config;
+}
+pub mod app {
+    use crate::first::Config as FirstConfig;
+    use crate::second as settings;
+    pub struct App { first: FirstConfig, second: settings::Config }
+}
+";
+    let krate = parse_source(source, false).unwrap();
+    assert_eq!(
+        krate
+            .relations
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            ("crate::app::App", "crate::first::Config"),
+            ("crate::app::App", "crate::second::Config")
+        ]
+    );
+    assert_eq!(
+        krate.root.modules[2].imports,
+        ["crate::first::Config", "crate::second"]
+    );
+}
+
+#[test]
+fn test_retain_visible_uses_module_reach() {
+    let source = r"
+pub struct Api { pub hidden: private_mod::Hidden }
+mod private_mod {
+    pub struct Hidden;
+    impl Hidden {
+        pub fn new() -> Self { Self }
+    }
+    mod nested {
+        pub struct Deep;
+    }
+}
+pub mod public_mod {
+    pub struct Visible;
+    pub(crate) mod internal {
+        pub struct Shared;
+    }
+}
+";
+    let mut krate = parse_source(source, false).unwrap();
+    assert_eq!(krate.root.visibility, Visibility::Public);
+    assert_eq!(krate.root.modules[0].visibility, Visibility::Private);
+
+    retain_visible(&mut krate, VisibilityLevel::Crate);
+    let paths = krate
+        .root
+        .walk()
+        .map(|m| m.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "crate",
+            "crate::private_mod",
+            "crate::public_mod",
+            "crate::public_mod::internal"
+        ],
+        "modules in the root are seen by the whole crate"
+    );
+    assert_eq!(krate.root.modules[0].structs[0].name, "Hidden");
+    assert_eq!(krate.root.modules[0].impls[0].functions[0].name, "new");
+    assert_eq!(krate.relations.len(), 1);
+
+    retain_visible(&mut krate, VisibilityLevel::Public);
+    let paths = krate
+        .root
+        .walk()
+        .map(|m| m.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, ["crate", "crate::public_mod"]);
+    assert_eq!(krate.root.modules[0].structs[0].name, "Visible");
+    assert!(krate.relations.is_empty());
+}
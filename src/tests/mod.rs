@@ -1,2 +1,3 @@
+mod lib_tests;
 mod logic_tests;
-mod main_tests;
+mod render_tests;
@@ -0,0 +1,1093 @@
+use crate::{Format, Generator, JSON_SCHEMA_VERSION, model::VisibilityLevel, render::layout};
+
+const SOURCE: &str = r"
+pub struct Point {
+    pub x: i32,
+    pub(crate) y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+";
+
+#[test]
+fn test_json_document() {
+    let json = Generator::from_source(SOURCE)
+        .format(Format::Json)
+        .generate()
+        .unwrap();
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(document["schema_version"], JSON_SCHEMA_VERSION);
+    let point = &document["crate"]["root"]["structs"][0];
+    assert_eq!(point["name"], "Point");
+    assert_eq!(point["location"]["line"], 2);
+    assert_eq!(point["fields"][0]["visibility"], "pub");
+    assert_eq!(point["fields"][1]["visibility"], "pub(crate)");
+
+    let new = &document["crate"]["root"]["impls"][0]["functions"][0];
+    assert_eq!(new["is_const"], true);
+    assert_eq!(new["return_type"], "Self");
+}
+
+#[test]
+fn test_mermaid_class_diagram() {
+    let mermaid = Generator::from_source(
+        r"
+pub struct Line {
+    pub start: Point,
+    end: Point,
+    callback: fn(u8) -> u8,
+}
+
+pub struct Point(pub i32, pub i32);
+
+impl Point {
+    pub fn origin() -> Self {
+        Self(0, 0)
+    }
+
+    pub fn x(&self) -> i32 {
+        self.0
+    }
+}
+
+enum Shape<T> {
+    Line(Line),
+    Circle { center: Point, radius: T },
+}
+",
+    )
+    .format(Format::Mermaid)
+    .generate()
+    .unwrap();
+
+    assert!(mermaid.starts_with("classDiagram\n"), "{mermaid}");
+    assert!(mermaid.contains("namespace crate {"), "{mermaid}");
+    assert!(mermaid.contains("+start: Point"), "{mermaid}");
+    assert!(
+        mermaid.contains("-callback: fn#40;u8#41; -> u8"),
+        "{mermaid}"
+    );
+    assert!(mermaid.contains("+origin()$ Self"), "{mermaid}");
+    assert!(mermaid.contains("+x(&self) i32"), "{mermaid}");
+    assert!(mermaid.contains("class Shape~T~ {"), "{mermaid}");
+    assert!(
+        mermaid.contains("Circle #123; center: Point, radius: T #125;"),
+        "{mermaid}"
+    );
+    assert!(mermaid.contains("Line --> Point"), "{mermaid}");
+    assert!(mermaid.contains("Shape --> Line"), "{mermaid}");
+}
+
+#[test]
+fn test_plantuml_packages() {
+    let plantuml = Generator::from_source(
+        r"
+mod shapes {
+    pub struct Circle {
+        pub(crate) radius: f64,
+        on_draw: Box<dyn Fn(f64)>,
+    }
+
+    impl Circle {
+        pub fn area(&self) -> f64 {
+            0.0
+        }
+    }
+
+    pub enum Kind {
+        Round,
+        Polygon(u8),
+    }
+}
+
+struct Canvas {
+    shapes: Vec<shapes::Circle>,
+}
+",
+    )
+    .format(Format::PlantUml)
+    .generate()
+    .unwrap();
+
+    assert!(plantuml.starts_with("@startuml\n"), "{plantuml}");
+    assert!(plantuml.ends_with("@enduml\n"), "{plantuml}");
+    assert!(plantuml.contains("  package shapes {"), "{plantuml}");
+    assert!(plantuml.contains("~radius : f64"), "{plantuml}");
+    assert!(
+        plantuml.contains("{field} -on_draw : Box<dyn Fn(f64)>"),
+        "{plantuml}"
+    );
+    assert!(plantuml.contains("+area(&self) : f64"), "{plantuml}");
+    assert!(plantuml.contains("enum Kind {"), "{plantuml}");
+    assert!(
+        plantuml.contains("crate::Canvas --> crate::shapes::Circle"),
+        "{plantuml}"
+    );
+}
+
+#[test]
+fn test_dot_clusters_and_records() {
+    let dot = Generator::from_source(
+        r"
+mod geometry {
+    pub struct Point { pub x: i32, pub y: i32 }
+
+    pub enum Shape {
+        Dot(Point),
+        Polygon { points: Vec<Point> },
+    }
+}
+",
+    )
+    .format(Format::Dot)
+    .generate()
+    .unwrap();
+
+    assert!(dot.starts_with("digraph \"crate\" {"), "{dot}");
+    assert!(dot.contains("subgraph \"cluster_crate\" {"), "{dot}");
+    assert!(
+        dot.contains("    subgraph \"cluster_crate__geometry\" {"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#""crate::geometry::Point" [label="{Point|+x: i32\l+y: i32\l|}"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r"Polygon \{ points: Vec\<Point\> \}\l"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#""crate::geometry::Shape" -> "crate::geometry::Point" [arrowhead=vee];"#),
+        "{dot}"
+    );
+}
+
+#[test]
+fn test_layout_places_used_types_below() {
+    let krate = Generator::from_source(
+        r"
+struct Leaf;
+struct Root { branch: Branch }
+struct Branch { leaves: Vec<Leaf> }
+fn grow(root: &mut Root) {}
+",
+    )
+    .parse()
+    .unwrap();
+    let layout = layout::layout(&krate);
+
+    let card = |title: &str| {
+        layout
+            .cards
+            .iter()
+            .find(|card| card.title == title)
+            .unwrap_or_else(|| panic!("{title} should have a card"))
+            .rect
+    };
+    assert!(card("Root").bottom() < card("Branch").y, "Root uses Branch");
+    assert!(card("Branch").bottom() < card("Leaf").y, "Branch uses Leaf");
+    assert!(
+        card("fn grow(root: &mut Root)").y > card("Leaf").bottom(),
+        "functions follow the types"
+    );
+    assert_eq!(layout.edges.len(), 2, "both relations should be routed");
+    assert!(
+        layout.edges.iter().all(|edge| edge.points.len() == 4),
+        "edges are routed orthogonally"
+    );
+}
+
+#[test]
+fn test_svg_document() {
+    let svg = Generator::from_source(
+        r"
+mod shapes {
+    pub struct Polygon<T> { pub points: Vec<T> }
+    pub enum Kind { Convex, Concave }
+    pub struct Shape { kind: Kind }
+}
+",
+    )
+    .format(Format::Svg)
+    .generate()
+    .unwrap();
+
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""),
+        "{svg}"
+    );
+    assert!(svg.ends_with("</svg>\n"), "{svg}");
+    assert!(
+        svg.contains(r#"<g class="module" data-path="crate::shapes">"#),
+        "{svg}"
+    );
+    assert!(svg.contains(">Polygon&lt;T&gt;</text>"), "{svg}");
+    assert!(svg.contains(">+ points: Vec&lt;T&gt;</text>"), "{svg}");
+    assert!(svg.contains(">• Convex</text>"), "{svg}");
+    assert!(svg.contains(r#"marker-end="url(#uses)""#), "{svg}");
+}
+
+#[test]
+fn test_markdown_report() {
+    let markdown = Generator::from_source(format!(
+        r"{SOURCE}
+pub enum Shape {{ Circle(f64), Rect {{ w: f64, h: f64 }} }}
+mod empty {{}}
+mod ops {{
+    pub fn apply(f: fn(u8) -> u8, x: u8) -> u8 {{ f(x) }}
+}}
+"
+    ))
+    .format(Format::Markdown)
+    .generate()
+    .unwrap();
+
+    assert!(markdown.contains("\n## `crate`\n"), "{markdown}");
+    assert!(markdown.contains("\n## `crate::ops`\n"), "{markdown}");
+    assert!(
+        !markdown.contains("crate::empty"),
+        "modules without items are skipped: {markdown}"
+    );
+    assert!(
+        markdown.contains("| `y` | `i32` | pub(crate) |"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("| `Rect` | `{ w: f64, h: f64 }` |"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("| `apply` | `pub fn apply(f: fn(u8) -> u8, x: u8) -> u8` |"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("- `impl Point`\n  - `pub const fn new(x: i32, y: i32) -> Self`"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_d2_diagram() {
+    let d2 = Generator::from_source(
+        r"
+mod shapes {
+    pub struct Polygon<T> { pub points: Vec<T> }
+    pub enum Kind { Convex, Named { label: &'static str } }
+    pub struct Style { kind: Kind }
+}
+#[cfg(test)]
+mod tests {
+    struct Fixture;
+}
+",
+    )
+    .format(Format::D2)
+    .generate()
+    .unwrap();
+
+    assert!(
+        d2.contains("  \"shapes\": {\n    label: \"shapes\"\n"),
+        "{d2}"
+    );
+    assert!(
+        d2.contains("\"Polygon\": {\n      shape: class\n      label: \"Polygon<T>\"\n"),
+        "{d2}"
+    );
+    assert!(d2.contains("\"+points\": \"Vec<T>\""), "{d2}");
+    assert!(
+        d2.contains("\"Named\": \"{ label: &'static str }\""),
+        "{d2}"
+    );
+    assert!(
+        d2.contains("\"crate\".\"shapes\".\"Style\" -> \"crate\".\"shapes\".\"Kind\""),
+        "{d2}"
+    );
+    assert!(!d2.contains("Fixture"), "test modules are excluded: {d2}");
+}
+
+#[test]
+fn test_drawio_pages() {
+    let drawio = Generator::from_source(
+        r"
+pub struct Config { pub shape: shapes::Shape }
+mod empty {}
+mod shapes {
+    pub struct Shape { kind: Kind }
+    pub enum Kind { Convex, Concave }
+}
+",
+    )
+    .format(Format::DrawIo)
+    .generate()
+    .unwrap();
+
+    assert!(drawio.starts_with("<mxfile host=\"diagen\">\n"), "{drawio}");
+    assert!(drawio.ends_with("</mxfile>\n"), "{drawio}");
+    assert_eq!(
+        drawio.matches("<diagram ").count(),
+        2,
+        "one page for the root and one per non-empty top-level module: {drawio}"
+    );
+    assert!(
+        drawio.contains(r#"<diagram id="page-1" name="shapes">"#),
+        "pages are numbered without the empty modules: {drawio}"
+    );
+    assert!(
+        drawio.contains(r#"value="- kind: Kind" style="text;"#),
+        "{drawio}"
+    );
+    assert!(
+        drawio.contains(r#"edge="1" parent="1" source="card-0" target="card-1">"#),
+        "{drawio}"
+    );
+}
+
+#[test]
+fn test_graphml_graph() {
+    let graphml = Generator::from_source(
+        r"
+pub(crate) struct Config { pub shape: shapes::Shape }
+mod shapes {
+    pub struct Shape;
+    pub fn area(shape: &Shape) -> f64 { 0.0 }
+}
+",
+    )
+    .format(Format::GraphMl)
+    .generate()
+    .unwrap();
+
+    assert!(graphml.ends_with("</graphml>\n"), "{graphml}");
+    assert!(
+        graphml.contains(r#"<node id="crate::Config"><data key="label">Config</data><data key="kind">struct</data><data key="visibility">pub(crate)</data><data key="module">crate</data><data key="line">2</data>"#),
+        "{graphml}"
+    );
+    assert!(
+        graphml.contains(r#"<node id="crate::shapes::area()">"#),
+        "{graphml}"
+    );
+    assert!(
+        graphml.contains(r#"source="crate::shapes" target="crate::shapes::Shape"><data key="relation">contains</data>"#),
+        "{graphml}"
+    );
+    assert!(
+        graphml.contains(
+            r#"source="crate" target="crate::shapes"><data key="relation">contains</data>"#
+        ),
+        "{graphml}"
+    );
+    assert!(
+        graphml.contains(r#"source="crate::Config" target="crate::shapes::Shape"><data key="relation">uses</data>"#),
+        "{graphml}"
+    );
+}
+
+#[test]
+fn test_structurizr_workspace() {
+    let dsl = Generator::from_source(
+        r#"
+//! Shape tools
+pub struct Config { pub shape: shapes::Shape }
+/// Geometric "shapes"
+mod shapes {
+    pub struct Shape { kind: kinds::Kind }
+}
+mod kinds {
+    use crate::shapes::Shape;
+    pub enum Kind { Convex, Concave }
+    pub trait Describe {}
+}
+mod ui {
+    pub struct Canvas { shapes: Vec<crate::shapes::Shape> }
+    impl crate::kinds::Describe for Canvas {}
+}
+"#,
+    )
+    .format(Format::Structurizr)
+    .generate()
+    .unwrap();
+
+    assert!(
+        dsl.contains(r#"crate = container "crate" "Shape tools" "Rust crate" {"#),
+        "{dsl}"
+    );
+    assert!(
+        dsl.contains(r#"crate_shapes = component "shapes" "Geometric \"shapes\"" "Rust module""#),
+        "{dsl}"
+    );
+    assert!(
+        dsl.contains(r#"crate_kinds -> crate_shapes "Imports from""#),
+        "{dsl}"
+    );
+    assert!(
+        dsl.contains(r#"crate_shapes -> crate_kinds "Uses types of""#),
+        "{dsl}"
+    );
+    assert!(
+        dsl.contains(r#"crate_ui -> crate_shapes "Uses types of""#),
+        "{dsl}"
+    );
+    assert!(
+        dsl.contains(r#"crate_ui -> crate_kinds "Implements traits of""#),
+        "{dsl}"
+    );
+    assert!(dsl.contains("component crate \"Components\" {"), "{dsl}");
+}
+
+#[test]
+fn test_excalidraw_scene() {
+    let scene = Generator::from_source(
+        r"
+pub struct Shape { kind: Kind }
+pub enum Kind { Convex, Concave }
+pub mod outer {
+    pub mod inner {
+        pub struct Point;
+    }
+}
+",
+    )
+    .format(Format::Excalidraw)
+    .generate()
+    .unwrap();
+    let scene: serde_json::Value = serde_json::from_str(&scene).unwrap();
+    let elements = scene["elements"].as_array().unwrap();
+    let element = |id: &str| {
+        elements
+            .iter()
+            .find(|element| element["id"] == id)
+            .unwrap_or_else(|| panic!("no element {id}"))
+    };
+
+    assert_eq!(scene["type"], "excalidraw");
+    assert_eq!(element("frame-0-name")["text"], "crate");
+    assert_eq!(element("card-0-title")["text"], "Shape");
+    assert_eq!(element("card-0-text-0")["text"], "- kind: Kind");
+    assert_eq!(element("card-1-text-0")["text"], "• Convex\n• Concave");
+
+    let arrow = element("edge-0");
+    assert_eq!(arrow["startBinding"]["elementId"], "card-0");
+    assert_eq!(arrow["endBinding"]["elementId"], "card-1");
+    assert_eq!(
+        element("card-1")["boundElements"][0]["id"],
+        "edge-0",
+        "cards know their arrows so they follow when moved"
+    );
+
+    assert_eq!(element("frame-2-name")["text"], "inner");
+    assert_eq!(
+        element("card-2")["groupIds"],
+        serde_json::json!(["card-2", "frame-2", "frame-1", "frame-0"]),
+        "cards move with every module around them"
+    );
+    assert_eq!(
+        element("card-2-title")["groupIds"],
+        element("card-2")["groupIds"]
+    );
+    assert_eq!(
+        element("frame-2")["groupIds"],
+        serde_json::json!(["frame-2", "frame-1", "frame-0"])
+    );
+    assert_eq!(
+        element("card-0")["groupIds"],
+        serde_json::json!(["card-0", "frame-0"])
+    );
+}
+
+#[test]
+#[expect(
+    clippy::literal_string_with_formatting_args,
+    reason = "LaTeX environments look like format arguments"
+)]
+fn test_tikz_document_and_fragment() {
+    let source = r"
+pub struct Parser<'a, T> { input: &'a str, items: Vec<T> }
+pub enum Token { Brace, Hash }
+";
+    let document = Generator::from_source(source)
+        .format(Format::Tikz)
+        .generate()
+        .unwrap();
+    let fragment = Generator::from_source(source)
+        .format(Format::TikzFragment)
+        .generate()
+        .unwrap();
+
+    assert!(
+        document.starts_with("\\documentclass[tikz, border=4pt]{standalone}\n\\begin{document}\n"),
+        "{document}"
+    );
+    assert!(document.ends_with("\\end{document}\n"), "{document}");
+    assert!(
+        document.contains(&fragment),
+        "the document wraps the fragment"
+    );
+    assert!(!fragment.contains("\\documentclass"), "{fragment}");
+    assert!(
+        fragment.contains("{Parser<\\textquotesingle{}a, T>}"),
+        "{fragment}"
+    );
+    assert!(
+        fragment.contains("- input: \\&\\textquotesingle{}a str\\\\"),
+        "{fragment}"
+    );
+    assert!(fragment.contains("\\textbullet{} Brace"), "{fragment}");
+    assert!(fragment.ends_with("\\end{tikzpicture}\n"), "{fragment}");
+}
+
+#[test]
+fn test_traits_section_and_interfaces() {
+    let source = r"
+pub trait Visitor: Sized {
+    type Output;
+    fn visit(&mut self, node: &Node) -> Self::Output;
+    fn finish(self) {}
+}
+pub struct Node;
+pub struct Printer;
+impl Visitor for Printer {
+    type Output = ();
+    fn visit(&mut self, node: &Node) {}
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    let traits = html.find("Traits").expect("a Traits section");
+    assert!(
+        html.find("Structs").unwrap() < traits,
+        "traits follow structs"
+    );
+    assert!(
+        html.contains(
+            r#"<div class="trait-name">Visitor<span class="trait-supertraits">: Sized</span></div>"#
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<div class="trait-associated-item">type Output</div>"#),
+        "{html}"
+    );
+    assert!(html.contains("Required methods"), "{html}");
+    assert!(html.contains("Provided methods"), "{html}");
+
+    let mermaid = Generator::from_source(source)
+        .format(Format::Mermaid)
+        .generate()
+        .unwrap();
+    assert!(
+        mermaid.contains(
+            "class Visitor {\n            <<interface>>\n            type Output\n            +visit#40;&mut self, node: &Node#41;)* Self::Output\n"
+        ) || mermaid.contains("+visit(&mut self, node: &Node)* Self::Output"),
+        "{mermaid}"
+    );
+    assert!(mermaid.contains("Printer ..|> Visitor"), "{mermaid}");
+
+    let plantuml = Generator::from_source(source)
+        .format(Format::PlantUml)
+        .generate()
+        .unwrap();
+    assert!(plantuml.contains("interface Visitor {"), "{plantuml}");
+    assert!(
+        plantuml.contains("{abstract} +visit(&mut self, node: &Node) : Self::Output"),
+        "{plantuml}"
+    );
+}
+
+#[test]
+fn test_declaration_sections() {
+    let source = r"
+pub type Map<V> = std::collections::HashMap<String, V>;
+pub const LIMITS: [u32; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+static mut CACHE: Option<u8> = None;
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    for section in ["Type Aliases", "Constants", "Statics"] {
+        assert!(
+            html.contains(&format!(
+                r#"<div class="item-section-header">{section}</div>"#
+            )),
+            "{html}"
+        );
+    }
+    assert!(
+        html.contains(r#"<span class="declaration-name">Map&lt;V&gt;</span>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"title="[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]""#),
+        "the full value is shown on hover: {html}"
+    );
+    assert!(
+        html.contains(r#"<span class="declaration-keyword">static mut</span>"#),
+        "{html}"
+    );
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains("| `Map<V>` | `std::collections::HashMap<String, V>` |"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("| `CACHE` | `Option<u8>` | `None` | yes |"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_unions_and_ffi_sections() {
+    let source = r#"
+pub union Bits {
+    pub raw: u32,
+    pub float: f32,
+}
+extern {
+    static VERSION: u32;
+    fn init() -> i32;
+}
+pub extern "C" fn on_event(id: u32) {}
+"#;
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(
+            r#"<div class="struct-name"><span class="union-keyword">union</span> Bits</div>"#
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<div class="item-section-header">FFI</div>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<div class="extern-block-abi">extern "C"</div>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="function-modifiers">extern &quot;C&quot;</span>"#),
+        "{html}"
+    );
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(markdown.contains("### Unions"), "{markdown}");
+    assert!(
+        markdown.contains("| static | `static VERSION: u32` |"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("| function | `fn init() -> i32` |"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_macros_section() {
+    let source = r"
+/// Square a number.
+///
+/// Evaluates <x> twice.
+#[macro_export]
+macro_rules! square {
+    ($x:expr) => { $x * $x };
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(r#"<div class="item-section-header">Macros</div>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="macro-exported">#[macro_export]</span>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(
+            r#"<details class="item-doc"><summary>Square a number.</summary><div class="item-doc-more">Evaluates &lt;x&gt; twice.</div></details>"#
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<div class="macro-arm">($x:expr)</div>"#),
+        "{html}"
+    );
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains(
+            "#### `square!`\n\nExported with `#[macro_export]`.\n\n> Square a number.\n>\n> Evaluates <x> twice.\n\n- `($x:expr)`\n"
+        ),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_impls_with_only_associated_items() {
+    let source = r"
+pub struct Meters(u32);
+pub struct ParseError;
+impl TryFrom<i64> for Meters {
+    type Error = ParseError;
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(r#"<div class="impl-associated-item">type Error = ParseError</div>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="impl-trait">TryFrom&lt;i64&gt;</span>"#),
+        "{html}"
+    );
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains("- `impl TryFrom<i64> for Meters`\n  - `type Error = ParseError`\n"),
+        "{markdown}"
+    );
+
+    let svg = Generator::from_source(source)
+        .format(Format::Svg)
+        .generate()
+        .unwrap();
+    assert!(svg.contains("type Error = ParseError"), "{svg}");
+}
+
+#[test]
+fn test_restricted_visibility_is_shown() {
+    let source = r"
+pub struct Settings {
+    pub name: String,
+    pub(crate) level: u8,
+    secret: String,
+}
+impl Settings {
+    pub(super) fn apply(&self) {}
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    let restricted = html
+        .find(r#"<div class="struct-restricted-fields">"#)
+        .expect("a group of restricted fields");
+    let level = html
+        .find(r#"<div class="struct-field-name">level</div>"#)
+        .unwrap();
+    let private = html.find(r#"<div class="struct-private-fields">"#).unwrap();
+    assert!(restricted < level && level < private, "{html}");
+    assert!(
+        html.contains(r#"<div class="struct-field" title="pub(crate)">"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="function-visibility">pub(super)</span>"#),
+        "{html}"
+    );
+
+    let public = Generator::from_source(source)
+        .min_visibility(VisibilityLevel::Public)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(public.contains("| `name` |"), "{public}");
+    assert!(!public.contains("level"), "{public}");
+    assert!(!public.contains("apply"), "{public}");
+}
+
+#[test]
+fn test_doc_comments_are_shown() {
+    let source = r"
+/// A point.
+///
+/// Used everywhere.
+pub struct Point {
+    /// Horizontal position
+    pub x: i32,
+    pub y: i32,
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(r#"<details class="item-doc"><summary>A point.</summary>"#),
+        "{html}"
+    );
+    assert!(html.contains(r#"title="Horizontal position""#), "{html}");
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains("> A point.\n>\n> Used everywhere.\n"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("| `x` | `i32` | pub | Horizontal position |\n| `y` | `i32` | pub |  |"),
+        "{markdown}"
+    );
+
+    let mermaid = Generator::from_source(source)
+        .format(Format::Mermaid)
+        .generate()
+        .unwrap();
+    assert!(
+        mermaid.contains(r#"note for Point "A point.""#),
+        "{mermaid}"
+    );
+}
+
+#[test]
+fn test_attribute_badges() {
+    let source = r"
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Mode { Fast }
+
+#[must_use]
+#[inline]
+pub fn mode() -> Mode { Mode::Fast }
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(
+            r##"<span class="item-badge item-derive" title="#[derive(Debug)]">Debug</span>"##
+        ),
+        "{html}"
+    );
+    assert!(html.contains(">#[non_exhaustive]</span>"), "{html}");
+    assert!(html.contains(">#[must_use]</span>"), "{html}");
+    assert!(!html.contains("#[inline]"), "{html}");
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .attributes(["derive"])
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains("#### `Mode`\n\n`#[derive(Debug, Clone)]`\n\n"),
+        "{markdown}"
+    );
+    assert!(!markdown.contains("non_exhaustive"), "{markdown}");
+}
+
+#[test]
+fn test_generic_signatures_are_shown() {
+    let source = r"
+pub fn first<T: Clone>(items: &[T]) -> Option<T> { todo!() }
+pub fn merge<K: Ord, V>(left: BTreeMap<K, V>, right: BTreeMap<K, V>) -> BTreeMap<K, V>
+where
+    V: Default,
+{
+    todo!()
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(
+            r#"<span class="function-name">first<span class="function-generics">&lt;T: Clone&gt;</span></span>"#
+        ),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="function-params">(<span class="function-param">items: &amp;[T]</span>)</span>"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="function-params function-params-wrapped">"#),
+        "{html}"
+    );
+    assert!(
+        html.contains(r#"<span class="function-where-predicate">V: Default,</span>"#),
+        "{html}"
+    );
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains("| `first` | `pub fn first<T: Clone>(items: &[T]) -> Option<T>` |"),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_enum_discriminants_are_shown() {
+    let source = r"
+#[repr(u8)]
+pub enum Opcode {
+    #[default]
+    Nop = 0,
+    Load = 0x10,
+}
+";
+    let html = Generator::from_source(source).generate().unwrap();
+    assert!(
+        html.contains(r#"<div class="enum-variant-discriminant">= 0x10</div>"#),
+        "{html}"
+    );
+    assert!(html.contains(">#[repr(u8)]</span>"), "{html}");
+    assert!(html.contains(">#[default]</span>"), "{html}");
+
+    let markdown = Generator::from_source(source)
+        .format(Format::Markdown)
+        .generate()
+        .unwrap();
+    assert!(
+        markdown.contains(
+            "| Variant | Data | Value | Attributes |\n| ------- | ---- | ----- | ---------- |\n| `Nop` |  | `0` | `#[default]` |\n| `Load` |  | `0x10` |  |\n"
+        ),
+        "{markdown}"
+    );
+}
+
+#[test]
+fn test_group_methods() {
+    let source = r"
+pub mod shapes {
+    pub struct Circle { pub radius: f64 }
+    pub enum Kind { Round }
+}
+mod behavior {
+    use crate::shapes::Circle;
+    impl Circle {
+        pub const UNIT: f64 = 1.0;
+        pub fn area(&self) -> f64 { todo!() }
+    }
+    impl std::fmt::Display for Circle {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { todo!() }
+    }
+}
+";
+    let separate = Generator::from_source(source).generate().unwrap();
+    assert!(
+        separate.contains(r#"<span class="impl-target">Circle</span>"#),
+        "{separate}"
+    );
+    assert!(!separate.contains(r#"class="type-members""#), "{separate}");
+
+    let html = Generator::from_source(source)
+        .group_methods(true)
+        .generate()
+        .unwrap();
+    let card = html
+        .split(r#"<div class="struct">"#)
+        .nth(1)
+        .and_then(|card| card.split(r#"<div class="item-section">"#).next())
+        .unwrap();
+    assert!(
+        card.contains(r#"<span class="type-implemented-trait">std::fmt::Display</span>"#),
+        "{card}"
+    );
+    assert!(
+        card.contains(r#"<div class="impl-associated-item">const UNIT: f64 = 1.0</div>"#),
+        "{card}"
+    );
+    assert!(
+        card.contains(r#"<span class="function-name">area</span>"#),
+        "{card}"
+    );
+    assert!(
+        !html.contains(r#"<span class="impl-target">Circle</span>"#),
+        "inherent impls are not shown separately: {html}"
+    );
+    assert!(
+        html.contains(r#"<span class="impl-target"> for Circle</span>"#),
+        "trait impls are still shown: {html}"
+    );
+}
+
+#[test]
+fn test_group_methods_matches_qualified_impls() {
+    let source = r"
+pub trait Shape {}
+pub struct Other { pub outer: u8 }
+impl inner::Other {
+    pub fn inner_method(&self) {}
+}
+impl Other {
+    pub fn outer_method(&self) {}
+}
+pub mod inner {
+    pub struct Other { pub inner: u8 }
+    impl super::Shape for Other {}
+}
+";
+    let krate = Generator::from_source(source).parse().unwrap();
+    assert_eq!(
+        krate
+            .root
+            .impls
+            .iter()
+            .map(|imp| imp.self_path.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("crate::inner::Other"), Some("crate::Other")]
+    );
+    assert_eq!(
+        krate.root.modules[0].impls[0].trait_path.as_deref(),
+        Some("crate::Shape")
+    );
+
+    let html = Generator::from_source(source)
+        .group_methods(true)
+        .generate()
+        .unwrap();
+    let card = |field: &str| {
+        html.split(r#"<div class="struct">"#)
+            .find(|card| card.contains(field))
+            .and_then(|card| card.split(r#"<div class="item-section">"#).next())
+            .unwrap()
+            .to_owned()
+    };
+    let outer = card(r#"<div class="struct-field-name">outer</div>"#);
+    let inner = card(r#"<div class="struct-field-name">inner</div>"#);
+    assert!(outer.contains(">outer_method<"), "{outer}");
+    assert!(!outer.contains(">inner_method<"), "{outer}");
+    assert!(inner.contains(">inner_method<"), "{inner}");
+    assert!(
+        inner.contains(r#"<span class="type-implemented-trait">super::Shape</span>"#),
+        "{inner}"
+    );
+}
+
+#[test]
+fn test_trait_impl_methods_have_the_trait_visibility() {
+    let source = r"
+pub(crate) trait Shape {
+    fn area(&self) -> f64;
+}
+pub struct Square;
+impl Shape for Square {
+    fn area(&self) -> f64 { 1.0 }
+}
+impl Clone for Square {
+    fn clone(&self) -> Self { Self }
+}
+";
+    let mermaid = Generator::from_source(source)
+        .format(Format::Mermaid)
+        .generate()
+        .unwrap();
+    assert!(mermaid.contains("~area(&self) f64"), "{mermaid}");
+    assert!(mermaid.contains("+clone(&self) Self"), "{mermaid}");
+    assert!(!mermaid.contains("-clone()"), "{mermaid}");
+
+    let plantuml = Generator::from_source(source)
+        .format(Format::PlantUml)
+        .generate()
+        .unwrap();
+    assert!(plantuml.contains("+clone(&self) : Self"), "{plantuml}");
+}